@@ -0,0 +1,34 @@
+//! Interface for contracts that gate access on a GitHub identity.
+//!
+//! The crate only builds as a `cdylib`, so consumers generate the client from
+//! the built wasm instead of depending on this crate:
+//!
+//! ```ignore
+//! mod github_identity {
+//!     soroban_sdk::contractimport!(file = "github_identity.wasm");
+//! }
+//!
+//! let identity = github_identity::Client::new(&env, &identity_contract);
+//! identity.require_min_tier(&user, &github_identity::Tier::Architect);
+//! ```
+
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::types::{Error, Tier};
+
+#[contractclient(name = "GithubIdentityGateClient")]
+pub trait GithubIdentityGate {
+    /// Whether `user` holds an identity of `min_tier` or above.
+    fn meets_tier(env: Env, user: Address, min_tier: Tier) -> bool;
+
+    /// Fails with `NoIdentityFound` when `user` holds no identity and `TierTooLow`
+    /// when its tier is below `min_tier`.
+    fn require_min_tier(env: Env, user: Address, min_tier: Tier) -> Result<(), Error>;
+
+    /// Like `meets_tier`, but `false` for identities in a flagged cluster.
+    fn meets_tier_unflagged(env: Env, user: Address, min_tier: Tier) -> bool;
+
+    /// Like `require_min_tier`, but also fails with `IdentityFlagged` for identities
+    /// in a flagged cluster.
+    fn require_min_tier_unflagged(env: Env, user: Address, min_tier: Tier) -> Result<(), Error>;
+}
@@ -1,14 +1,21 @@
 #![no_std]
 
+pub mod gate;
 mod storage;
 mod types;
 
 #[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, token, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
 
-pub use types::{Error, GithubData, Tier};
+pub use types::{
+    BadgeDefinition, BatchMintEntry, BatchMintResult, Cluster, CreditEventKind, CreditSummary,
+    DeveloperMetrics, Error, GithubData, GuardianSet, HistoryEntry, IdentityStatus, LanguageShare,
+    OrgMembership, OrgRole, RecoveryRequest, ReputationWeights, ScoreWeights, Tier, Vouch,
+};
 
 #[contract]
 pub struct GithubIdentityContract;
@@ -43,65 +50,108 @@ impl GithubIdentityContract {
         _signature: BytesN<64>,
         username: String,
         contributions: u32,
+        metrics: DeveloperMetrics,
+        languages: Vec<LanguageShare>,
         proof_data: Bytes,
         _referrer: Option<Address>,
         nonce: u64,
     ) -> Result<u64, Error> {
         caller.require_auth();
 
-        if username.len() == 0 {
-            return Err(Error::EmptyUsername);
-        }
-
-        if storage::has_identity(&env, &caller) {
-            return Err(Error::AlreadyHasIdentity);
-        }
-
-        let expected_nonce = storage::get_nonce(&env, &caller);
-        if nonce != expected_nonce {
-            return Err(Error::InvalidNonce);
-        }
-
         let _ = _signature;
 
-        let mint_fee = storage::get_mint_fee(&env);
-        if mint_fee > 0 {
-            return Err(Error::InsufficientPayment);
-        }
+        let entry = BatchMintEntry {
+            recipient: caller.clone(),
+            username,
+            contributions,
+            metrics,
+            languages,
+            proof_data,
+        };
+        Self::mint_identity(&env, Some(&caller), entry, nonce)
+    }
 
-        storage::increment_nonce(&env, &caller);
+    /// Mints an identity for `recipient` with `sponsor` paying the mint fee. Both
+    /// must authorize; `nonce` is the recipient's.
+    pub fn mint_for(
+        env: Env,
+        sponsor: Address,
+        recipient: Address,
+        _signature: BytesN<64>,
+        username: String,
+        contributions: u32,
+        metrics: DeveloperMetrics,
+        languages: Vec<LanguageShare>,
+        proof_data: Bytes,
+        nonce: u64,
+    ) -> Result<u64, Error> {
+        sponsor.require_auth();
+        recipient.require_auth();
 
-        let token_id = storage::get_next_token_id(&env);
-        storage::increment_token_counter(&env);
+        let _ = _signature;
 
-        let tier = Tier::from_contributions(contributions);
-        let github_data = GithubData {
-            username: username.clone(),
+        let entry = BatchMintEntry {
+            recipient: recipient.clone(),
+            username,
             contributions,
-            tier: tier.clone(),
-            minted_at: env.ledger().timestamp(),
-            updated_at: env.ledger().timestamp(),
+            metrics,
+            languages,
             proof_data,
         };
-
-        storage::set_token_data(&env, token_id, &github_data);
-        storage::set_holder_token(&env, &caller, token_id);
-        storage::set_has_identity(&env, &caller, true);
+        let token_id = Self::mint_identity(&env, Some(&sponsor), entry, nonce)?;
 
         env.events().publish(
-            (Symbol::new(&env, "identity_minted"),),
-            (caller, token_id, username, contributions, tier),
+            (Symbol::new(&env, "identity_sponsored"),),
+            (sponsor, recipient, token_id, storage::get_mint_fee(&env)),
         );
 
         Ok(token_id)
     }
 
+    /// Mints identities from pre-verified data without fees or recipient signatures.
+    /// Recipients that already hold an identity are skipped and reported.
+    pub fn batch_mint(
+        env: Env,
+        caller: Address,
+        entries: Vec<BatchMintEntry>,
+    ) -> Result<BatchMintResult, Error> {
+        caller.require_auth();
+        if !storage::is_minter(&env, &caller) && Self::assert_admin(&env, &caller).is_err() {
+            return Err(Error::NotMinter);
+        }
+        if entries.len() > types::MAX_MINT_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let mut result = BatchMintResult {
+            minted: Vec::new(&env),
+            skipped: Vec::new(&env),
+        };
+        for entry in entries.iter() {
+            if storage::has_identity(&env, &entry.recipient) {
+                result.skipped.push_back(entry.recipient);
+                continue;
+            }
+
+            let nonce = storage::get_nonce(&env, &entry.recipient);
+            let token_id = Self::mint_identity(&env, None, entry, nonce)?;
+            result.minted.push_back(token_id);
+        }
+        Ok(result)
+    }
+
+    pub fn is_minter(env: Env, minter: Address) -> bool {
+        storage::is_minter(&env, &minter)
+    }
+
     pub fn update_token(
         env: Env,
         caller: Address,
         token_id: u64,
         username: String,
         contributions: u32,
+        metrics: DeveloperMetrics,
+        languages: Vec<LanguageShare>,
         proof_data: Bytes,
     ) -> Result<(), Error> {
         caller.require_auth();
@@ -111,16 +161,28 @@ impl GithubIdentityContract {
             return Err(Error::Unauthorized);
         }
 
-        let tier = Tier::from_contributions(contributions);
+        types::validate_username(&username)?;
+        types::validate_languages(&languages)?;
 
         let mut data = storage::get_token_data(&env, token_id)?;
+        let now = env.ledger().timestamp();
+        let cooldown = storage::get_update_cooldown(&env);
+        if now < data.updated_at.saturating_add(cooldown) {
+            return Err(Error::TooSoon);
+        }
+
+        let tier = Self::tier_for(&env, token_id, contributions, &metrics, now);
+
         data.username = username.clone();
         data.contributions = contributions;
         data.tier = tier.clone();
-        data.updated_at = env.ledger().timestamp();
+        data.metrics = metrics;
+        data.languages = languages;
+        data.updated_at = now;
         data.proof_data = proof_data;
 
         storage::update_token_data(&env, token_id, &data)?;
+        Self::record_history(&env, token_id, &data, now);
 
         env.events().publish(
             (Symbol::new(&env, "identity_updated"),),
@@ -130,18 +192,112 @@ impl GithubIdentityContract {
         Ok(())
     }
 
+    pub fn recompute_tier(env: Env, token_id: u64) -> Result<Tier, Error> {
+        Self::apply_current_tier(&env, token_id)?;
+        Ok(storage::get_token_data(&env, token_id)?.tier)
+    }
+
     pub fn get_token_data(env: Env, token_id: u64) -> Result<GithubData, Error> {
         storage::get_token_data(&env, token_id)
     }
 
+    pub fn get_history(
+        env: Env,
+        token_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<HistoryEntry>, Error> {
+        storage::get_token_data(&env, token_id)?;
+
+        let history = storage::get_history(&env, token_id);
+        let start = start.min(history.len());
+        let end = start
+            .saturating_add(limit.min(types::MAX_HISTORY_PAGE))
+            .min(history.len());
+        Ok(history.slice(start..end))
+    }
+
+    pub fn get_history_len(env: Env, token_id: u64) -> Result<u32, Error> {
+        storage::get_token_data(&env, token_id)?;
+        Ok(storage::get_history(&env, token_id).len())
+    }
+
+    pub fn get_languages(env: Env, token_id: u64) -> Result<Vec<LanguageShare>, Error> {
+        Ok(storage::get_token_data(&env, token_id)?.languages)
+    }
+
     pub fn get_user_token(env: Env, user: Address) -> Result<u64, Error> {
         storage::get_holder_token(&env, &user)
     }
 
+    /// SEP-50 addresses tokens by `u32`; ids are issued sequentially from 1 and widen
+    /// losslessly to the `u64` used in storage.
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, Error> {
+        storage::get_token_owner(&env, token_id.into())
+    }
+
     pub fn has_identity(env: Env, user: Address) -> bool {
         storage::has_identity(&env, &user)
     }
 
+    pub fn has_fresh_identity(env: Env, user: Address) -> bool {
+        match storage::get_holder_token(&env, &user) {
+            Ok(token_id) => Self::status_of(&env, token_id) == Ok(IdentityStatus::Active),
+            Err(_) => false,
+        }
+    }
+
+    pub fn meets_tier(env: Env, user: Address, min_tier: Tier) -> bool {
+        Self::require_min_tier(env, user, min_tier).is_ok()
+    }
+
+    pub fn require_min_tier(env: Env, user: Address, min_tier: Tier) -> Result<(), Error> {
+        let token_id = storage::get_holder_token(&env, &user)?;
+        let data = storage::get_token_data(&env, token_id)?;
+        if !data.tier.at_least(&min_tier) {
+            return Err(Error::TierTooLow);
+        }
+        Ok(())
+    }
+
+    pub fn meets_tier_unflagged(env: Env, user: Address, min_tier: Tier) -> bool {
+        Self::require_min_tier_unflagged(env, user, min_tier).is_ok()
+    }
+
+    /// Like `require_min_tier`, but also fails with `IdentityFlagged` when the
+    /// identity belongs to a flagged cluster.
+    pub fn require_min_tier_unflagged(
+        env: Env,
+        user: Address,
+        min_tier: Tier,
+    ) -> Result<(), Error> {
+        Self::require_min_tier(env.clone(), user.clone(), min_tier)?;
+        let token_id = storage::get_holder_token(&env, &user)?;
+        if storage::get_token_cluster(&env, token_id).is_some() {
+            return Err(Error::IdentityFlagged);
+        }
+        Ok(())
+    }
+
+    pub fn get_status(env: Env, token_id: u64) -> Result<IdentityStatus, Error> {
+        Self::status_of(&env, token_id)
+    }
+
+    pub fn get_validity_period(env: Env) -> u64 {
+        storage::get_validity_period(&env)
+    }
+
+    pub fn get_update_cooldown(env: Env) -> u64 {
+        storage::get_update_cooldown(&env)
+    }
+
+    pub fn next_update_allowed_at(env: Env, token_id: u64) -> Result<u64, Error> {
+        let data = storage::get_token_data(&env, token_id)?;
+        Ok(data
+            .updated_at
+            .saturating_add(storage::get_update_cooldown(&env)))
+    }
+
     pub fn get_nonce(env: Env, user: Address) -> u64 {
         storage::get_nonce(&env, &user)
     }
@@ -152,7 +308,120 @@ impl GithubIdentityContract {
 
     pub fn get_token_svg(env: Env, token_id: u64) -> Result<String, Error> {
         let data = storage::get_token_data(&env, token_id)?;
-        Ok(types::generate_svg(&env, &data))
+        Ok(Self::render_svg(&env, token_id, &data))
+    }
+
+    pub fn get_tier_thresholds(env: Env) -> Vec<u32> {
+        storage::get_tier_thresholds(&env)
+    }
+
+    pub fn get_min_account_age(env: Env) -> u64 {
+        storage::get_min_account_age(&env)
+    }
+
+    pub fn get_score_weights(env: Env) -> ScoreWeights {
+        storage::get_score_weights(&env)
+    }
+
+    pub fn developer_score(env: Env, token_id: u64) -> Result<u32, Error> {
+        let data = storage::get_token_data(&env, token_id)?;
+        Ok(storage::get_score_weights(&env).score(data.contributions, &data.metrics))
+    }
+
+    pub fn activity_score(env: Env, token_id: u64) -> Result<u32, Error> {
+        let data = storage::get_token_data(&env, token_id)?;
+        let score = storage::get_score_weights(&env).score(data.contributions, &data.metrics);
+        Ok(Self::decayed_score(
+            &env,
+            token_id,
+            score,
+            data.contributions,
+            data.updated_at,
+        ))
+    }
+
+    /// Bounded 0..=`MAX_REPUTATION` score for pricing risk; 0 for addresses without
+    /// an identity.
+    pub fn reputation_score(env: Env, user: Address) -> u32 {
+        storage::get_holder_token(&env, &user)
+            .and_then(|token_id| Self::reputation_of(&env, token_id))
+            .unwrap_or(0)
+    }
+
+    pub fn get_reputation_weights(env: Env) -> ReputationWeights {
+        storage::get_reputation_weights(&env)
+    }
+
+    pub fn get_activity_tiering(env: Env) -> bool {
+        storage::get_activity_tiering(&env)
+    }
+
+    pub fn get_svg_template(env: Env, tier: Tier) -> Option<String> {
+        storage::get_svg_template(&env, &tier)
+    }
+
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, types::TOKEN_NAME)
+    }
+
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, types::TOKEN_SYMBOL)
+    }
+
+    pub fn balance(env: Env, owner: Address) -> u32 {
+        if storage::has_identity(&env, &owner) {
+            1
+        } else {
+            0
+        }
+    }
+
+    pub fn token_uri(env: Env, token_id: u32) -> Result<String, Error> {
+        let token_id = u64::from(token_id);
+        let data = storage::get_token_data(&env, token_id)?;
+        let svg = Self::render_svg(&env, token_id, &data);
+        Ok(types::token_uri(&env, token_id, &data, &svg))
+    }
+
+    pub fn transfer(_env: Env, _from: Address, _to: Address, _token_id: u32) -> Result<(), Error> {
+        Err(Error::TransferNotAllowed)
+    }
+
+    pub fn transfer_from(
+        _env: Env,
+        _spender: Address,
+        _from: Address,
+        _to: Address,
+        _token_id: u32,
+    ) -> Result<(), Error> {
+        Err(Error::TransferNotAllowed)
+    }
+
+    pub fn approve(
+        _env: Env,
+        _approver: Address,
+        _approved: Address,
+        _token_id: u32,
+        _live_until_ledger: u32,
+    ) -> Result<(), Error> {
+        Err(Error::TransferNotAllowed)
+    }
+
+    pub fn approve_for_all(
+        _env: Env,
+        _owner: Address,
+        _operator: Address,
+        _live_until_ledger: u32,
+    ) -> Result<(), Error> {
+        Err(Error::TransferNotAllowed)
+    }
+
+    pub fn get_approved(_env: Env, _token_id: u32) -> Option<Address> {
+        None
+    }
+
+    pub fn is_approved_for_all(_env: Env, _owner: Address, _operator: Address) -> bool {
+        false
     }
 
     pub fn list_tokens_of_user(env: Env, user: Address) -> Vec<u64> {
@@ -162,40 +431,1010 @@ impl GithubIdentityContract {
         }
     }
 
-    pub fn set_mint_fee(env: Env, admin: Address, new_fee: i128) -> Result<(), Error> {
-        admin.require_auth();
-        Self::assert_admin(&env, &admin)?;
+    /// Awards a badge to a token. Callable by the admin or an attester, never by
+    /// the holder, so badges cannot be self-awarded.
+    pub fn award_badge(
+        env: Env,
+        attester: Address,
+        token_id: u64,
+        badge_id: u32,
+    ) -> Result<(), Error> {
+        attester.require_auth();
+        Self::assert_admin_or_attester(&env, &attester)?;
 
-        let mut config = storage::get_config(&env)?;
-        config.mint_fee = new_fee;
-        storage::set_config(&env, &config);
+        storage::get_token_data(&env, token_id)?;
+        storage::get_badge(&env, badge_id)?;
+
+        let mut badges = storage::get_token_badges(&env, token_id);
+        if badges.contains(badge_id) {
+            return Err(Error::BadgeAlreadyAwarded);
+        }
+        if badges.len() >= types::MAX_BADGES_PER_TOKEN {
+            return Err(Error::TooManyBadges);
+        }
+        badges.push_back(badge_id);
+        storage::set_token_badges(&env, token_id, &badges);
+
+        env.events().publish(
+            (Symbol::new(&env, "badge_awarded"),),
+            (token_id, badge_id, attester),
+        );
         Ok(())
     }
 
-    pub fn set_access_control(
+    pub fn is_attester(env: Env, attester: Address) -> bool {
+        storage::is_attester(&env, &attester)
+    }
+
+    pub fn list_badges(env: Env, token_id: u64) -> Result<Vec<u32>, Error> {
+        storage::get_token_data(&env, token_id)?;
+        Ok(storage::get_token_badges(&env, token_id))
+    }
+
+    pub fn get_badge(env: Env, badge_id: u32) -> Result<BadgeDefinition, Error> {
+        storage::get_badge(&env, badge_id)
+    }
+
+    pub fn attest_org(
         env: Env,
-        admin: Address,
-        access_control: Address,
+        attester: Address,
+        token_id: u64,
+        membership: OrgMembership,
     ) -> Result<(), Error> {
-        admin.require_auth();
-        Self::assert_admin(&env, &admin)?;
+        attester.require_auth();
+        Self::assert_admin_or_attester(&env, &attester)?;
 
-        let mut config = storage::get_config(&env)?;
-        config.access_control = access_control;
-        storage::set_config(&env, &config);
+        storage::get_token_data(&env, token_id)?;
+
+        let now = env.ledger().timestamp();
+        membership.validate(now)?;
+
+        // Re-attesting an org replaces its claim; expired claims are dropped to free room.
+        let mut orgs = Vec::new(&env);
+        for org in storage::get_orgs(&env, token_id).iter() {
+            if org.org_id != membership.org_id && !org.is_expired(now) {
+                orgs.push_back(org);
+            }
+        }
+        if orgs.len() >= types::MAX_ORGS_PER_TOKEN {
+            return Err(Error::TooManyOrgs);
+        }
+        orgs.push_back(membership.clone());
+        storage::set_orgs(&env, token_id, &orgs);
+
+        env.events().publish(
+            (Symbol::new(&env, "org_attested"),),
+            (
+                token_id,
+                membership.org_id,
+                membership.role,
+                membership.expires_at,
+                attester,
+            ),
+        );
         Ok(())
     }
 
-    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), Error> {
-        admin.require_auth();
-        Self::assert_admin(&env, &admin)?;
+    pub fn get_orgs(env: Env, token_id: u64) -> Result<Vec<OrgMembership>, Error> {
+        storage::get_token_data(&env, token_id)?;
 
-        let mut config = storage::get_config(&env)?;
-        config.treasury = treasury;
-        storage::set_config(&env, &config);
+        let now = env.ledger().timestamp();
+        let mut orgs = Vec::new(&env);
+        for org in storage::get_orgs(&env, token_id).iter() {
+            if !org.is_expired(now) {
+                orgs.push_back(org);
+            }
+        }
+        Ok(orgs)
+    }
+
+    pub fn is_member(env: Env, token_id: u64, org_id: u64) -> bool {
+        let now = env.ledger().timestamp();
+        storage::get_orgs(&env, token_id)
+            .iter()
+            .any(|org| org.org_id == org_id && !org.is_expired(now))
+    }
+
+    pub fn report_event(
+        env: Env,
+        reporter: Address,
+        user: Address,
+        kind: CreditEventKind,
+        amount: i128,
+    ) -> Result<(), Error> {
+        reporter.require_auth();
+        if !storage::is_reporter(&env, &reporter) {
+            return Err(Error::NotReporter);
+        }
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let token_id = storage::get_holder_token(&env, &user)?;
+        let now = env.ledger().timestamp();
+        let mut summary = storage::get_credit_summary(&env, token_id);
+        summary.record(&kind, amount, now);
+        storage::set_credit_summary(&env, token_id, &summary);
+
+        env.events().publish(
+            (Symbol::new(&env, "credit_reported"), token_id),
+            (reporter, user, kind, amount, now),
+        );
+        Ok(())
+    }
+
+    pub fn get_credit_summary(env: Env, token_id: u64) -> Result<CreditSummary, Error> {
+        storage::get_token_data(&env, token_id)?;
+        Ok(storage::get_credit_summary(&env, token_id))
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        storage::is_reporter(&env, &reporter)
+    }
+
+    pub fn vouch(
+        env: Env,
+        voucher: Address,
+        vouchee_token: u64,
+        weight: u32,
+        stake: i128,
+    ) -> Result<(), Error> {
+        voucher.require_auth();
+
+        let voucher_token = storage::get_holder_token(&env, &voucher)?;
+        let voucher_data = storage::get_token_data(&env, voucher_token)?;
+        if !voucher_data.tier.at_least(&types::MIN_VOUCHER_TIER) {
+            return Err(Error::TierTooLow);
+        }
+        storage::get_token_data(&env, vouchee_token)?;
+        if voucher_token == vouchee_token || weight == 0 || weight > types::MAX_VOUCH_WEIGHT {
+            return Err(Error::InvalidVouch);
+        }
+        if stake < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut given = storage::get_vouches_given(&env, voucher_token);
+        if given.contains(vouchee_token) {
+            return Err(Error::AlreadyVouched);
+        }
+        if given.len() >= types::MAX_VOUCHES_PER_VOUCHER {
+            return Err(Error::TooManyVouches);
+        }
+
+        let mut vouches = storage::get_vouches(&env, vouchee_token);
+        if vouches.len() >= types::MAX_VOUCHES_RECEIVED {
+            return Err(Error::TooManyVouches);
+        }
+
+        let stake_token = if stake > 0 {
+            let stake_token = storage::get_stake_token(&env).ok_or(Error::InvalidAmount)?;
+            token::Client::new(&env, &stake_token).transfer(
+                &voucher,
+                &env.current_contract_address(),
+                &stake,
+            );
+            Some(stake_token)
+        } else {
+            None
+        };
+
+        given.push_back(vouchee_token);
+        storage::set_vouches_given(&env, voucher_token, &given);
+        vouches.push_back(Vouch {
+            voucher: voucher_token,
+            weight,
+            stake,
+            stake_token,
+            created_at: env.ledger().timestamp(),
+        });
+        storage::set_vouches(&env, vouchee_token, &vouches);
+
+        env.events().publish(
+            (Symbol::new(&env, "vouched"),),
+            (voucher_token, vouchee_token, weight, stake),
+        );
+        Ok(())
+    }
+
+    /// Withdraws a vouch and returns its stake to the voucher.
+    pub fn revoke_vouch(env: Env, voucher: Address, vouchee_token: u64) -> Result<(), Error> {
+        voucher.require_auth();
+
+        let voucher_token = storage::get_holder_token(&env, &voucher)?;
+        let mut given = storage::get_vouches_given(&env, voucher_token);
+        let given_index = given
+            .first_index_of(vouchee_token)
+            .ok_or(Error::VouchNotFound)?;
+        given.remove(given_index);
+        storage::set_vouches_given(&env, voucher_token, &given);
+
+        let mut vouches = storage::get_vouches(&env, vouchee_token);
+        let index = vouches
+            .iter()
+            .position(|vouch| vouch.voucher == voucher_token)
+            .ok_or(Error::VouchNotFound)? as u32;
+        let vouch = vouches.get_unchecked(index);
+        vouches.remove(index);
+        storage::set_vouches(&env, vouchee_token, &vouches);
+
+        if let Some(stake_token) = vouch.stake_token {
+            token::Client::new(&env, &stake_token).transfer(
+                &env.current_contract_address(),
+                &voucher,
+                &vouch.stake,
+            );
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "vouch_revoked"),),
+            (voucher_token, vouchee_token),
+        );
         Ok(())
     }
 
+    pub fn get_vouches(env: Env, token_id: u64) -> Result<Vec<Vouch>, Error> {
+        storage::get_token_data(&env, token_id)?;
+        Ok(storage::get_vouches(&env, token_id))
+    }
+
+    pub fn get_fee_token(env: Env) -> Option<Address> {
+        storage::get_fee_token(&env)
+    }
+
+    pub fn get_stake_token(env: Env) -> Option<Address> {
+        storage::get_stake_token(&env)
+    }
+
+    /// Links `token_ids` into a new flagged cluster. Callable by the admin or a
+    /// reporter.
+    pub fn flag_cluster(
+        env: Env,
+        caller: Address,
+        token_ids: Vec<u64>,
+        reason: String,
+    ) -> Result<u64, Error> {
+        caller.require_auth();
+        Self::assert_admin_or_reporter(&env, &caller)?;
+        types::validate_cluster_reason(&reason)?;
+        if token_ids.len() < 2 {
+            return Err(Error::InvalidCluster);
+        }
+
+        let cluster_id = storage::get_next_cluster_id(&env);
+        storage::increment_cluster_counter(&env);
+
+        let mut cluster = Cluster {
+            id: cluster_id,
+            token_ids: Vec::new(&env),
+            reason,
+            flagged_by: caller,
+            flagged_at: env.ledger().timestamp(),
+        };
+        Self::link_tokens(&env, &mut cluster, &token_ids)?;
+        Ok(cluster_id)
+    }
+
+    /// Adds more tokens to an existing cluster. Callable by the admin or a reporter.
+    pub fn add_to_cluster(
+        env: Env,
+        caller: Address,
+        cluster_id: u64,
+        token_ids: Vec<u64>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::assert_admin_or_reporter(&env, &caller)?;
+
+        let mut cluster = storage::get_cluster(&env, cluster_id)?;
+        Self::link_tokens(&env, &mut cluster, &token_ids)
+    }
+
+    pub fn get_cluster(env: Env, token_id: u64) -> Option<Cluster> {
+        let cluster_id = storage::get_token_cluster(&env, token_id)?;
+        storage::get_cluster(&env, cluster_id).ok()
+    }
+
+    pub fn set_guardians(
+        env: Env,
+        caller: Address,
+        guardians: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let token_id = storage::get_holder_token(&env, &caller)?;
+        if storage::has_recovery(&env, token_id) {
+            return Err(Error::RecoveryPending);
+        }
+
+        let guardian_set = GuardianSet {
+            guardians,
+            threshold,
+        };
+        guardian_set.validate(&caller)?;
+
+        storage::set_guardians(&env, token_id, &guardian_set);
+
+        env.events().publish(
+            (Symbol::new(&env, "guardians_set"),),
+            (caller, token_id, guardian_set.guardians.len(), threshold),
+        );
+
+        Ok(())
+    }
+
+    pub fn initiate_recovery(
+        env: Env,
+        guardian: Address,
+        holder: Address,
+        new_owner: Address,
+    ) -> Result<(), Error> {
+        guardian.require_auth();
+
+        let token_id = storage::get_holder_token(&env, &holder)?;
+        Self::assert_guardian(&env, token_id, &guardian)?;
+
+        if storage::has_recovery(&env, token_id) {
+            return Err(Error::RecoveryPending);
+        }
+        if storage::has_identity(&env, &new_owner) {
+            return Err(Error::AlreadyHasIdentity);
+        }
+
+        let request = RecoveryRequest {
+            new_owner: new_owner.clone(),
+            approvals: Vec::from_array(&env, [guardian.clone()]),
+            objections: Vec::new(&env),
+            initiated_at: env.ledger().timestamp(),
+        };
+        storage::set_recovery(&env, token_id, &request);
+
+        env.events().publish(
+            (Symbol::new(&env, "recovery_initiated"),),
+            (holder, token_id, new_owner, guardian),
+        );
+
+        Ok(())
+    }
+
+    pub fn approve_recovery(env: Env, guardian: Address, holder: Address) -> Result<(), Error> {
+        guardian.require_auth();
+
+        let token_id = storage::get_holder_token(&env, &holder)?;
+        Self::assert_guardian(&env, token_id, &guardian)?;
+
+        let mut request = storage::get_recovery(&env, token_id)?;
+        if request.approvals.contains(&guardian) {
+            return Err(Error::AlreadyApproved);
+        }
+        if let Some(index) = request.objections.first_index_of(&guardian) {
+            request.objections.remove(index);
+        }
+        request.approvals.push_back(guardian.clone());
+        storage::set_recovery(&env, token_id, &request);
+
+        env.events().publish(
+            (Symbol::new(&env, "recovery_approved"),),
+            (holder, token_id, guardian),
+        );
+
+        Ok(())
+    }
+
+    /// Records a guardian's objection to the pending request, withdrawing any
+    /// approval they gave it. Once `threshold` guardians object the request is
+    /// dropped; a new one must then be opened with `initiate_recovery`.
+    pub fn object_recovery(env: Env, guardian: Address, holder: Address) -> Result<(), Error> {
+        guardian.require_auth();
+
+        let token_id = storage::get_holder_token(&env, &holder)?;
+        Self::assert_guardian(&env, token_id, &guardian)?;
+
+        let mut request = storage::get_recovery(&env, token_id)?;
+        if request.objections.contains(&guardian) {
+            return Err(Error::AlreadyObjected);
+        }
+        if let Some(index) = request.approvals.first_index_of(&guardian) {
+            request.approvals.remove(index);
+        }
+        request.objections.push_back(guardian.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "recovery_objected"),),
+            (holder.clone(), token_id, guardian),
+        );
+
+        let guardian_set = storage::get_guardians(&env, token_id).ok_or(Error::NotGuardian)?;
+        if request.objections.len() < guardian_set.threshold {
+            storage::set_recovery(&env, token_id, &request);
+            return Ok(());
+        }
+
+        storage::remove_recovery(&env, token_id);
+        env.events().publish(
+            (Symbol::new(&env, "recovery_cancelled"),),
+            (holder, token_id),
+        );
+
+        Ok(())
+    }
+
+    pub fn cancel_recovery(env: Env, holder: Address) -> Result<(), Error> {
+        holder.require_auth();
+
+        let token_id = storage::get_holder_token(&env, &holder)?;
+        storage::get_recovery(&env, token_id)?;
+        storage::remove_recovery(&env, token_id);
+
+        env.events().publish(
+            (Symbol::new(&env, "recovery_cancelled"),),
+            (holder, token_id),
+        );
+
+        Ok(())
+    }
+
+    pub fn execute_recovery(env: Env, holder: Address) -> Result<(), Error> {
+        let token_id = storage::get_holder_token(&env, &holder)?;
+        let request = storage::get_recovery(&env, token_id)?;
+        let guardian_set = storage::get_guardians(&env, token_id).ok_or(Error::NotGuardian)?;
+
+        if !request.is_ready(guardian_set.threshold, env.ledger().timestamp()) {
+            return Err(Error::RecoveryNotReady);
+        }
+        if storage::has_identity(&env, &request.new_owner) {
+            return Err(Error::AlreadyHasIdentity);
+        }
+
+        storage::remove_holder_token(&env, &holder);
+        storage::set_has_identity(&env, &holder, false);
+        storage::set_holder_token(&env, &request.new_owner, token_id);
+        storage::set_token_owner(&env, token_id, &request.new_owner);
+        storage::set_has_identity(&env, &request.new_owner, true);
+        storage::remove_recovery(&env, token_id);
+
+        env.events().publish(
+            (Symbol::new(&env, "identity_recovered"),),
+            (holder, request.new_owner, token_id),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_guardians(env: Env, holder: Address) -> Result<GuardianSet, Error> {
+        let token_id = storage::get_holder_token(&env, &holder)?;
+        let guardian_set = storage::get_guardians(&env, token_id).unwrap_or(GuardianSet {
+            guardians: Vec::new(&env),
+            threshold: 0,
+        });
+        Ok(guardian_set)
+    }
+
+    pub fn get_recovery(env: Env, holder: Address) -> Result<RecoveryRequest, Error> {
+        let token_id = storage::get_holder_token(&env, &holder)?;
+        storage::get_recovery(&env, token_id)
+    }
+
+    pub fn set_mint_fee(env: Env, admin: Address, new_fee: i128) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let mut config = storage::get_config(&env)?;
+        config.mint_fee = new_fee;
+        storage::set_config(&env, &config);
+        Ok(())
+    }
+
+    pub fn set_access_control(
+        env: Env,
+        admin: Address,
+        access_control: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let mut config = storage::get_config(&env)?;
+        config.access_control = access_control;
+        storage::set_config(&env, &config);
+        Ok(())
+    }
+
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let mut config = storage::get_config(&env)?;
+        config.treasury = treasury;
+        storage::set_config(&env, &config);
+        Ok(())
+    }
+
+    pub fn set_fee_token(env: Env, admin: Address, token: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_fee_token(&env, &token);
+
+        env.events()
+            .publish((Symbol::new(&env, "fee_token_set"),), token);
+        Ok(())
+    }
+
+    pub fn set_stake_token(env: Env, admin: Address, token: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_stake_token(&env, &token);
+
+        env.events()
+            .publish((Symbol::new(&env, "stake_token_set"),), token);
+        Ok(())
+    }
+
+    pub fn add_minter(env: Env, admin: Address, minter: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_minter(&env, &minter, true);
+
+        env.events()
+            .publish((Symbol::new(&env, "minter_added"),), minter);
+        Ok(())
+    }
+
+    pub fn remove_minter(env: Env, admin: Address, minter: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_minter(&env, &minter, false);
+
+        env.events()
+            .publish((Symbol::new(&env, "minter_removed"),), minter);
+        Ok(())
+    }
+
+    pub fn add_reporter(env: Env, admin: Address, reporter: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_reporter(&env, &reporter, true);
+
+        env.events()
+            .publish((Symbol::new(&env, "reporter_added"),), reporter);
+        Ok(())
+    }
+
+    pub fn remove_reporter(env: Env, admin: Address, reporter: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_reporter(&env, &reporter, false);
+
+        env.events()
+            .publish((Symbol::new(&env, "reporter_removed"),), reporter);
+        Ok(())
+    }
+
+    pub fn add_attester(env: Env, admin: Address, attester: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_attester(&env, &attester, true);
+
+        env.events()
+            .publish((Symbol::new(&env, "attester_added"),), attester);
+        Ok(())
+    }
+
+    pub fn remove_attester(env: Env, admin: Address, attester: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_attester(&env, &attester, false);
+
+        env.events()
+            .publish((Symbol::new(&env, "attester_removed"),), attester);
+        Ok(())
+    }
+
+    /// Dissolves a cluster, clearing the flag from all of its tokens.
+    pub fn clear_cluster(env: Env, admin: Address, cluster_id: u64) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let cluster = storage::get_cluster(&env, cluster_id)?;
+        for token_id in cluster.token_ids.iter() {
+            storage::remove_token_cluster(&env, token_id);
+        }
+        storage::remove_cluster(&env, cluster_id);
+
+        env.events()
+            .publish((Symbol::new(&env, "cluster_cleared"),), cluster_id);
+        Ok(())
+    }
+
+    pub fn set_tier_thresholds(
+        env: Env,
+        admin: Address,
+        thresholds: Vec<u32>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        types::validate_thresholds(&thresholds)?;
+        storage::set_tier_thresholds(&env, &thresholds);
+
+        env.events()
+            .publish((Symbol::new(&env, "tier_thresholds_set"),), thresholds);
+        Ok(())
+    }
+
+    pub fn set_score_weights(env: Env, admin: Address, weights: ScoreWeights) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        weights.validate()?;
+        storage::set_score_weights(&env, &weights);
+
+        env.events()
+            .publish((Symbol::new(&env, "score_weights_set"),), weights);
+        Ok(())
+    }
+
+    pub fn set_reputation_weights(
+        env: Env,
+        admin: Address,
+        weights: ReputationWeights,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        weights.validate()?;
+        storage::set_reputation_weights(&env, &weights);
+
+        env.events()
+            .publish((Symbol::new(&env, "reputation_weights_set"),), weights);
+        Ok(())
+    }
+
+    pub fn set_min_account_age(env: Env, admin: Address, seconds: u64) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_min_account_age(&env, seconds);
+
+        env.events()
+            .publish((Symbol::new(&env, "min_account_age_set"),), seconds);
+        Ok(())
+    }
+
+    pub fn set_activity_tiering(env: Env, admin: Address, enabled: bool) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_activity_tiering(&env, enabled);
+
+        env.events()
+            .publish((Symbol::new(&env, "activity_tiering_set"),), enabled);
+        Ok(())
+    }
+
+    pub fn set_validity_period(env: Env, admin: Address, seconds: u64) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_validity_period(&env, seconds);
+
+        env.events()
+            .publish((Symbol::new(&env, "validity_period_set"),), seconds);
+        Ok(())
+    }
+
+    pub fn set_update_cooldown(env: Env, admin: Address, seconds: u64) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::set_update_cooldown(&env, seconds);
+
+        env.events()
+            .publish((Symbol::new(&env, "update_cooldown_set"),), seconds);
+        Ok(())
+    }
+
+    pub fn recompute_tiers(env: Env, admin: Address, token_ids: Vec<u64>) -> Result<u32, Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        if token_ids.len() > types::MAX_RECOMPUTE_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let mut changed = 0u32;
+        for token_id in token_ids.iter() {
+            if Self::apply_current_tier(&env, token_id)? {
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+
+    pub fn set_svg_template(
+        env: Env,
+        admin: Address,
+        tier: Tier,
+        template: String,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        types::validate_template(&template)?;
+        storage::set_svg_template(&env, &tier, &template);
+
+        env.events()
+            .publish((Symbol::new(&env, "svg_template_set"),), tier);
+        Ok(())
+    }
+
+    pub fn remove_svg_template(env: Env, admin: Address, tier: Tier) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        storage::remove_svg_template(&env, &tier);
+
+        env.events()
+            .publish((Symbol::new(&env, "svg_template_removed"),), tier);
+        Ok(())
+    }
+
+    pub fn define_badge(
+        env: Env,
+        admin: Address,
+        badge_id: u32,
+        badge: BadgeDefinition,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+        badge.validate()?;
+
+        storage::set_badge(&env, badge_id, &badge);
+
+        env.events().publish(
+            (Symbol::new(&env, "badge_defined"),),
+            (badge_id, badge.name),
+        );
+        Ok(())
+    }
+
+    /// Shared minting path. `payer` is charged the mint fee; `None` waives it.
+    fn mint_identity(
+        env: &Env,
+        payer: Option<&Address>,
+        entry: BatchMintEntry,
+        nonce: u64,
+    ) -> Result<u64, Error> {
+        let BatchMintEntry {
+            recipient,
+            username,
+            contributions,
+            metrics,
+            languages,
+            proof_data,
+        } = entry;
+        types::validate_username(&username)?;
+        types::validate_languages(&languages)?;
+
+        if storage::has_identity(env, &recipient) {
+            return Err(Error::AlreadyHasIdentity);
+        }
+
+        let expected_nonce = storage::get_nonce(env, &recipient);
+        if nonce != expected_nonce {
+            return Err(Error::InvalidNonce);
+        }
+
+        metrics.check_account_age(storage::get_min_account_age(env), env.ledger().timestamp())?;
+
+        if let Some(payer) = payer {
+            Self::collect_mint_fee(env, payer)?;
+        }
+
+        storage::increment_nonce(env, &recipient);
+
+        let token_id = storage::get_next_token_id(env);
+        storage::increment_token_counter(env);
+
+        let now = env.ledger().timestamp();
+        let tier = Self::tier_for(env, token_id, contributions, &metrics, now);
+        let github_data = GithubData {
+            username: username.clone(),
+            contributions,
+            tier: tier.clone(),
+            metrics,
+            languages,
+            minted_at: now,
+            updated_at: now,
+            proof_data,
+        };
+
+        storage::set_token_data(env, token_id, &github_data);
+        Self::record_history(env, token_id, &github_data, now);
+        storage::set_holder_token(env, &recipient, token_id);
+        storage::set_token_owner(env, token_id, &recipient);
+        storage::set_has_identity(env, &recipient, true);
+
+        env.events().publish(
+            (Symbol::new(env, "identity_minted"),),
+            (recipient, token_id, username, contributions, tier),
+        );
+
+        Ok(token_id)
+    }
+
+    /// Transfers the mint fee from `payer` to the treasury. A non-zero fee cannot be
+    /// paid until the admin has set the fee token.
+    fn collect_mint_fee(env: &Env, payer: &Address) -> Result<(), Error> {
+        let mint_fee = storage::get_mint_fee(env);
+        if mint_fee <= 0 {
+            return Ok(());
+        }
+
+        let fee_token = storage::get_fee_token(env).ok_or(Error::InsufficientPayment)?;
+        let treasury = storage::get_treasury(env)?;
+        token::Client::new(env, &fee_token).transfer(payer, &treasury, &mint_fee);
+        Ok(())
+    }
+
+    fn tier_for(
+        env: &Env,
+        token_id: u64,
+        contributions: u32,
+        metrics: &DeveloperMetrics,
+        updated_at: u64,
+    ) -> Tier {
+        let mut score = storage::get_score_weights(env).score(contributions, metrics);
+        if storage::get_activity_tiering(env) {
+            score = Self::decayed_score(env, token_id, score, contributions, updated_at);
+        }
+        Tier::from_thresholds(score, &storage::get_tier_thresholds(env))
+    }
+
+    fn decayed_score(
+        env: &Env,
+        token_id: u64,
+        score: u32,
+        contributions: u32,
+        updated_at: u64,
+    ) -> u32 {
+        let now = env.ledger().timestamp();
+        let history = storage::get_history(env, token_id);
+        let recent_delta = types::recent_contribution_delta(&history, contributions, now);
+        types::activity_score(score, recent_delta, now.saturating_sub(updated_at))
+    }
+
+    fn apply_current_tier(env: &Env, token_id: u64) -> Result<bool, Error> {
+        let mut data = storage::get_token_data(env, token_id)?;
+        let tier = Self::tier_for(
+            env,
+            token_id,
+            data.contributions,
+            &data.metrics,
+            data.updated_at,
+        );
+        if tier == data.tier {
+            return Ok(false);
+        }
+
+        let previous = data.tier;
+        data.tier = tier.clone();
+        storage::update_token_data(env, token_id, &data)?;
+        Self::record_history(env, token_id, &data, env.ledger().timestamp());
+
+        env.events().publish(
+            (Symbol::new(env, "tier_changed"),),
+            (token_id, previous, tier),
+        );
+        Ok(true)
+    }
+
+    fn status_of(env: &Env, token_id: u64) -> Result<IdentityStatus, Error> {
+        let data = storage::get_token_data(env, token_id)?;
+        Ok(IdentityStatus::at(
+            data.updated_at,
+            storage::get_validity_period(env),
+            env.ledger().timestamp(),
+        ))
+    }
+
+    fn reputation_of(env: &Env, token_id: u64) -> Result<u32, Error> {
+        let data = storage::get_token_data(env, token_id)?;
+        let now = env.ledger().timestamp();
+        let thresholds = storage::get_tier_thresholds(env);
+        let factors = types::ReputationFactors {
+            tier: data.tier,
+            developer_score: storage::get_score_weights(env)
+                .score(data.contributions, &data.metrics),
+            score_ceiling: thresholds.last().unwrap_or(1),
+            tier_ceiling: thresholds.len(),
+            identity_age: now.saturating_sub(data.minted_at),
+            fresh: IdentityStatus::at(data.updated_at, storage::get_validity_period(env), now)
+                == IdentityStatus::Active,
+            credit: storage::get_credit_summary(env, token_id),
+            vouch_weight: storage::get_vouches(env, token_id)
+                .iter()
+                .fold(0u32, |total, vouch| total.saturating_add(vouch.weight)),
+            flagged: storage::get_token_cluster(env, token_id).is_some(),
+        };
+        Ok(storage::get_reputation_weights(env).score(&factors))
+    }
+
+    fn record_history(env: &Env, token_id: u64, data: &GithubData, timestamp: u64) {
+        let entry = HistoryEntry {
+            timestamp,
+            contributions: data.contributions,
+            tier: data.tier.clone(),
+        };
+        storage::append_history(env, token_id, &entry);
+    }
+
+    fn render_svg(env: &Env, token_id: u64, data: &GithubData) -> String {
+        let mut badges = Vec::new(env);
+        for badge_id in storage::get_token_badges(env, token_id).iter() {
+            if let Ok(badge) = storage::get_badge(env, badge_id) {
+                badges.push_back(badge.art);
+            }
+        }
+
+        match storage::get_svg_template(env, &data.tier) {
+            Some(template) => types::render_template(env, &template, token_id, data, &badges),
+            None => types::generate_svg(env, token_id, data, &badges),
+        }
+    }
+
+    fn assert_guardian(env: &Env, token_id: u64, guardian: &Address) -> Result<(), Error> {
+        let guardian_set = storage::get_guardians(env, token_id).ok_or(Error::NotGuardian)?;
+        if !guardian_set.guardians.contains(guardian) {
+            return Err(Error::NotGuardian);
+        }
+        Ok(())
+    }
+
+    fn link_tokens(env: &Env, cluster: &mut Cluster, token_ids: &Vec<u64>) -> Result<(), Error> {
+        if token_ids.len() > types::MAX_CLUSTER_LINK_BATCH
+            || cluster.token_ids.len() + token_ids.len() > types::MAX_CLUSTER_SIZE
+        {
+            return Err(Error::BatchTooLarge);
+        }
+        for token_id in token_ids.iter() {
+            storage::get_token_data(env, token_id)?;
+            if storage::get_token_cluster(env, token_id).is_some() {
+                return Err(Error::AlreadyFlagged);
+            }
+            storage::set_token_cluster(env, token_id, cluster.id);
+            cluster.token_ids.push_back(token_id);
+        }
+        storage::set_cluster(env, cluster);
+
+        env.events().publish(
+            (Symbol::new(env, "cluster_flagged"), cluster.id),
+            (token_ids.clone(), cluster.reason.clone()),
+        );
+        Ok(())
+    }
+
+    fn assert_admin_or_reporter(env: &Env, caller: &Address) -> Result<(), Error> {
+        if storage::is_reporter(env, caller) || Self::assert_admin(env, caller).is_ok() {
+            return Ok(());
+        }
+        Err(Error::NotReporter)
+    }
+
+    fn assert_admin_or_attester(env: &Env, caller: &Address) -> Result<(), Error> {
+        if storage::is_attester(env, caller) || Self::assert_admin(env, caller).is_ok() {
+            return Ok(());
+        }
+        Err(Error::NotAttester)
+    }
+
     fn assert_admin(env: &Env, caller: &Address) -> Result<(), Error> {
         let stored_admin = storage::get_admin(env)?;
         if caller != &stored_admin {
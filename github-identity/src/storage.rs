@@ -1,9 +1,23 @@
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
-use crate::types::{Config, Error, GithubData};
+use crate::types::{
+    BadgeDefinition, Cluster, Config, CreditSummary, Error, GithubData, GuardianSet, HistoryEntry,
+    OrgMembership, RecoveryRequest, ReputationWeights, ScoreWeights, Tier, Vouch,
+    DEFAULT_TIER_THRESHOLDS, MAX_HISTORY_ENTRIES,
+};
 
 const KEY_CONFIG: &str = "CONFIG";
 const KEY_TOKEN_COUNTER: &str = "TOKEN_CTR";
+const KEY_TIER_THRESHOLDS: &str = "TIER_THR";
+const KEY_SCORE_WEIGHTS: &str = "SCORE_WT";
+const KEY_REPUTATION_WEIGHTS: &str = "REP_WT";
+const KEY_MIN_ACCOUNT_AGE: &str = "MIN_AGE";
+const KEY_ACTIVITY_TIERING: &str = "ACT_TIER";
+const KEY_VALIDITY_PERIOD: &str = "VALIDITY";
+const KEY_STAKE_TOKEN: &str = "STAKE_TK";
+const KEY_FEE_TOKEN: &str = "FEE_TK";
+const KEY_CLUSTER_COUNTER: &str = "CLS_CTR";
+const KEY_UPDATE_COOLDOWN: &str = "COOLDOWN";
 
 const THIRTY_DAYS_IN_LEDGERS: u32 = 518_400;
 
@@ -34,6 +48,111 @@ pub fn get_mint_fee(env: &Env) -> i128 {
     get_config(env).map(|c| c.mint_fee).unwrap_or(0)
 }
 
+pub fn set_tier_thresholds(env: &Env, thresholds: &Vec<u32>) {
+    env.storage()
+        .persistent()
+        .set(&KEY_TIER_THRESHOLDS, thresholds);
+}
+
+pub fn get_tier_thresholds(env: &Env) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&KEY_TIER_THRESHOLDS)
+        .unwrap_or(Vec::from_array(env, DEFAULT_TIER_THRESHOLDS))
+}
+
+pub fn set_score_weights(env: &Env, weights: &ScoreWeights) {
+    env.storage().persistent().set(&KEY_SCORE_WEIGHTS, weights);
+}
+
+pub fn get_score_weights(env: &Env) -> ScoreWeights {
+    env.storage()
+        .persistent()
+        .get(&KEY_SCORE_WEIGHTS)
+        .unwrap_or_default()
+}
+
+pub fn set_reputation_weights(env: &Env, weights: &ReputationWeights) {
+    env.storage()
+        .persistent()
+        .set(&KEY_REPUTATION_WEIGHTS, weights);
+}
+
+pub fn get_reputation_weights(env: &Env) -> ReputationWeights {
+    env.storage()
+        .persistent()
+        .get(&KEY_REPUTATION_WEIGHTS)
+        .unwrap_or_default()
+}
+
+pub fn set_min_account_age(env: &Env, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&KEY_MIN_ACCOUNT_AGE, &seconds);
+}
+
+pub fn get_min_account_age(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&KEY_MIN_ACCOUNT_AGE)
+        .unwrap_or(0)
+}
+
+pub fn set_activity_tiering(env: &Env, enabled: bool) {
+    env.storage()
+        .persistent()
+        .set(&KEY_ACTIVITY_TIERING, &enabled);
+}
+
+pub fn get_activity_tiering(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&KEY_ACTIVITY_TIERING)
+        .unwrap_or(false)
+}
+
+pub fn set_validity_period(env: &Env, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&KEY_VALIDITY_PERIOD, &seconds);
+}
+
+pub fn get_validity_period(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&KEY_VALIDITY_PERIOD)
+        .unwrap_or(0)
+}
+
+pub fn set_update_cooldown(env: &Env, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&KEY_UPDATE_COOLDOWN, &seconds);
+}
+
+pub fn get_update_cooldown(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&KEY_UPDATE_COOLDOWN)
+        .unwrap_or(0)
+}
+
+pub fn set_fee_token(env: &Env, token: &Address) {
+    env.storage().persistent().set(&KEY_FEE_TOKEN, token);
+}
+
+pub fn get_fee_token(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&KEY_FEE_TOKEN)
+}
+
+pub fn set_stake_token(env: &Env, token: &Address) {
+    env.storage().persistent().set(&KEY_STAKE_TOKEN, token);
+}
+
+pub fn get_stake_token(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&KEY_STAKE_TOKEN)
+}
+
 pub fn get_next_token_id(env: &Env) -> u64 {
     env.storage()
         .persistent()
@@ -48,6 +167,20 @@ pub fn increment_token_counter(env: &Env) {
         .set(&KEY_TOKEN_COUNTER, &(current + 1));
 }
 
+pub fn get_next_cluster_id(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&KEY_CLUSTER_COUNTER)
+        .unwrap_or(1u64)
+}
+
+pub fn increment_cluster_counter(env: &Env) {
+    let current = get_next_cluster_id(env);
+    env.storage()
+        .persistent()
+        .set(&KEY_CLUSTER_COUNTER, &(current + 1));
+}
+
 pub fn set_token_data(env: &Env, token_id: u64, data: &GithubData) {
     let key = (Symbol::new(env, "TOK"), token_id);
     env.storage().persistent().set(&key, data);
@@ -70,6 +203,26 @@ pub fn update_token_data(env: &Env, token_id: u64, data: &GithubData) -> Result<
     Ok(())
 }
 
+pub fn get_history(env: &Env, token_id: u64) -> Vec<HistoryEntry> {
+    let key = (Symbol::new(env, "HST"), token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Appends to the token's history, dropping the oldest entry once full.
+pub fn append_history(env: &Env, token_id: u64, entry: &HistoryEntry) {
+    let mut history = get_history(env, token_id);
+    if history.len() >= MAX_HISTORY_ENTRIES {
+        history.pop_front();
+    }
+    history.push_back(entry.clone());
+
+    let key = (Symbol::new(env, "HST"), token_id);
+    env.storage().persistent().set(&key, &history);
+}
+
 pub fn set_holder_token(env: &Env, holder: &Address, token_id: u64) {
     let key = (Symbol::new(env, "HLD"), holder.clone());
     env.storage().persistent().set(&key, &token_id);
@@ -83,6 +236,24 @@ pub fn get_holder_token(env: &Env, holder: &Address) -> Result<u64, Error> {
         .ok_or(Error::NoIdentityFound)
 }
 
+pub fn remove_holder_token(env: &Env, holder: &Address) {
+    let key = (Symbol::new(env, "HLD"), holder.clone());
+    env.storage().persistent().remove(&key);
+}
+
+pub fn set_token_owner(env: &Env, token_id: u64, owner: &Address) {
+    let key = (Symbol::new(env, "OWN"), token_id);
+    env.storage().persistent().set(&key, owner);
+}
+
+pub fn get_token_owner(env: &Env, token_id: u64) -> Result<Address, Error> {
+    let key = (Symbol::new(env, "OWN"), token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::TokenNotFound)
+}
+
 pub fn set_has_identity(env: &Env, holder: &Address, has: bool) {
     let key = (Symbol::new(env, "HAS"), holder.clone());
     env.storage().persistent().set(&key, &has);
@@ -96,6 +267,204 @@ pub fn has_identity(env: &Env, holder: &Address) -> bool {
         .unwrap_or(false)
 }
 
+pub fn set_svg_template(env: &Env, tier: &Tier, template: &String) {
+    let key = (Symbol::new(env, "TPL"), tier.clone());
+    env.storage().persistent().set(&key, template);
+}
+
+pub fn get_svg_template(env: &Env, tier: &Tier) -> Option<String> {
+    let key = (Symbol::new(env, "TPL"), tier.clone());
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_svg_template(env: &Env, tier: &Tier) {
+    let key = (Symbol::new(env, "TPL"), tier.clone());
+    env.storage().persistent().remove(&key);
+}
+
+pub fn set_badge(env: &Env, badge_id: u32, badge: &BadgeDefinition) {
+    let key = (Symbol::new(env, "BDG"), badge_id);
+    env.storage().persistent().set(&key, badge);
+}
+
+pub fn get_badge(env: &Env, badge_id: u32) -> Result<BadgeDefinition, Error> {
+    let key = (Symbol::new(env, "BDG"), badge_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::BadgeNotFound)
+}
+
+pub fn set_token_badges(env: &Env, token_id: u64, badges: &Vec<u32>) {
+    let key = (Symbol::new(env, "BDS"), token_id);
+    env.storage().persistent().set(&key, badges);
+}
+
+pub fn get_token_badges(env: &Env, token_id: u64) -> Vec<u32> {
+    let key = (Symbol::new(env, "BDS"), token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_orgs(env: &Env, token_id: u64, orgs: &Vec<OrgMembership>) {
+    let key = (Symbol::new(env, "ORG"), token_id);
+    env.storage().persistent().set(&key, orgs);
+}
+
+pub fn get_orgs(env: &Env, token_id: u64) -> Vec<OrgMembership> {
+    let key = (Symbol::new(env, "ORG"), token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_minter(env: &Env, minter: &Address, allowed: bool) {
+    let key = (Symbol::new(env, "MNT"), minter.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_minter(env: &Env, minter: &Address) -> bool {
+    let key = (Symbol::new(env, "MNT"), minter.clone());
+    env.storage().persistent().has(&key)
+}
+
+pub fn set_reporter(env: &Env, reporter: &Address, allowed: bool) {
+    let key = (Symbol::new(env, "RPT"), reporter.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_reporter(env: &Env, reporter: &Address) -> bool {
+    let key = (Symbol::new(env, "RPT"), reporter.clone());
+    env.storage().persistent().has(&key)
+}
+
+pub fn set_attester(env: &Env, attester: &Address, allowed: bool) {
+    let key = (Symbol::new(env, "ATT"), attester.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_attester(env: &Env, attester: &Address) -> bool {
+    let key = (Symbol::new(env, "ATT"), attester.clone());
+    env.storage().persistent().has(&key)
+}
+
+pub fn set_credit_summary(env: &Env, token_id: u64, summary: &CreditSummary) {
+    let key = (Symbol::new(env, "CRD"), token_id);
+    env.storage().persistent().set(&key, summary);
+}
+
+pub fn get_credit_summary(env: &Env, token_id: u64) -> CreditSummary {
+    let key = (Symbol::new(env, "CRD"), token_id);
+    env.storage().persistent().get(&key).unwrap_or_default()
+}
+
+pub fn set_vouches(env: &Env, token_id: u64, vouches: &Vec<Vouch>) {
+    let key = (Symbol::new(env, "VCH"), token_id);
+    env.storage().persistent().set(&key, vouches);
+}
+
+pub fn get_vouches(env: &Env, token_id: u64) -> Vec<Vouch> {
+    let key = (Symbol::new(env, "VCH"), token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_vouches_given(env: &Env, token_id: u64, vouchees: &Vec<u64>) {
+    let key = (Symbol::new(env, "VCG"), token_id);
+    env.storage().persistent().set(&key, vouchees);
+}
+
+pub fn get_vouches_given(env: &Env, token_id: u64) -> Vec<u64> {
+    let key = (Symbol::new(env, "VCG"), token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_cluster(env: &Env, cluster: &Cluster) {
+    let key = (Symbol::new(env, "CLS"), cluster.id);
+    env.storage().persistent().set(&key, cluster);
+}
+
+pub fn get_cluster(env: &Env, cluster_id: u64) -> Result<Cluster, Error> {
+    let key = (Symbol::new(env, "CLS"), cluster_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::ClusterNotFound)
+}
+
+pub fn remove_cluster(env: &Env, cluster_id: u64) {
+    let key = (Symbol::new(env, "CLS"), cluster_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn set_token_cluster(env: &Env, token_id: u64, cluster_id: u64) {
+    let key = (Symbol::new(env, "CLT"), token_id);
+    env.storage().persistent().set(&key, &cluster_id);
+}
+
+pub fn get_token_cluster(env: &Env, token_id: u64) -> Option<u64> {
+    let key = (Symbol::new(env, "CLT"), token_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_token_cluster(env: &Env, token_id: u64) {
+    let key = (Symbol::new(env, "CLT"), token_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn set_guardians(env: &Env, token_id: u64, guardians: &GuardianSet) {
+    let key = (Symbol::new(env, "GRD"), token_id);
+    env.storage().persistent().set(&key, guardians);
+}
+
+pub fn get_guardians(env: &Env, token_id: u64) -> Option<GuardianSet> {
+    let key = (Symbol::new(env, "GRD"), token_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_recovery(env: &Env, token_id: u64, request: &RecoveryRequest) {
+    let key = (Symbol::new(env, "REC"), token_id);
+    env.storage().persistent().set(&key, request);
+}
+
+pub fn get_recovery(env: &Env, token_id: u64) -> Result<RecoveryRequest, Error> {
+    let key = (Symbol::new(env, "REC"), token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NoRecoveryPending)
+}
+
+pub fn has_recovery(env: &Env, token_id: u64) -> bool {
+    let key = (Symbol::new(env, "REC"), token_id);
+    env.storage().persistent().has(&key)
+}
+
+pub fn remove_recovery(env: &Env, token_id: u64) {
+    let key = (Symbol::new(env, "REC"), token_id);
+    env.storage().persistent().remove(&key);
+}
+
 pub fn get_nonce(env: &Env, user: &Address) -> u64 {
     let key = (Symbol::new(env, "NON"), user.clone());
     env.storage().temporary().get(&key).unwrap_or(0u64)
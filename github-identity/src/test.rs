@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Vec,
+};
 
 struct TestEnv {
     env: Env,
@@ -40,17 +43,60 @@ fn stub_signature(env: &Env) -> BytesN<64> {
 }
 
 fn mint_for(ctx: &TestEnv, user: &Address, username: &str, contributions: u32) -> u64 {
+    mint_with_metrics(
+        ctx,
+        user,
+        username,
+        contributions,
+        &DeveloperMetrics::default(),
+    )
+}
+
+fn mint_with_metrics(
+    ctx: &TestEnv,
+    user: &Address,
+    username: &str,
+    contributions: u32,
+    metrics: &DeveloperMetrics,
+) -> u64 {
     ctx.client.mint(
         user,
         &stub_signature(&ctx.env),
         &String::from_str(&ctx.env, username),
         &contributions,
+        metrics,
+        &Vec::new(&ctx.env),
         &Bytes::new(&ctx.env),
         &None,
         &ctx.client.get_nonce(user),
     )
 }
 
+fn update_contributions(ctx: &TestEnv, user: &Address, token_id: u64, contributions: u32) {
+    let username = ctx.client.get_token_data(&token_id).username;
+    ctx.client.update_token(
+        user,
+        &token_id,
+        &username,
+        &contributions,
+        &DeveloperMetrics::default(),
+        &Vec::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+    );
+}
+
+fn sample_metrics() -> DeveloperMetrics {
+    DeveloperMetrics {
+        merged_prs: 200,
+        reviews: 80,
+        repos_owned: 12,
+        stars_received: 1_000,
+        followers: 150,
+        account_created_at: 1_400_000_000,
+        longest_streak: 45,
+    }
+}
+
 #[test]
 fn test_initialize_sets_mint_fee() {
     let env = Env::default();
@@ -123,6 +169,19 @@ fn test_mint_empty_username_fails() {
     mint_for(&ctx, &user, "", 1500);
 }
 
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_mint_username_too_long_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    mint_for(
+        &ctx,
+        &user,
+        "a-username-that-is-longer-than-github-allows",
+        1500,
+    );
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #1)")]
 fn test_mint_twice_same_user_fails() {
@@ -144,12 +203,75 @@ fn test_mint_wrong_nonce_fails() {
         &stub_signature(&ctx.env),
         &String::from_str(&ctx.env, "devfelipenunes"),
         &1500u32,
+        &DeveloperMetrics::default(),
+        &Vec::new(&ctx.env),
         &Bytes::new(&ctx.env),
         &None,
         &99u64,
     );
 }
 
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn test_mint_without_min_account_age_accepts_any_account() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+
+    assert_eq!(ctx.client.get_min_account_age(), 0);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert!(ctx.client.has_identity(&user));
+}
+
+#[test]
+fn test_mint_old_enough_account_succeeds() {
+    let ctx = setup();
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_700_000_000);
+    ctx.client.set_min_account_age(&ctx.admin, &(30 * DAY));
+
+    let user = Address::generate(&ctx.env);
+    let metrics = DeveloperMetrics {
+        account_created_at: 1_700_000_000 - 30 * DAY,
+        ..DeveloperMetrics::default()
+    };
+    mint_with_metrics(&ctx, &user, "devfelipenunes", 1500, &metrics);
+    assert!(ctx.client.has_identity(&user));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn test_mint_account_too_new_fails() {
+    let ctx = setup();
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_700_000_000);
+    ctx.client.set_min_account_age(&ctx.admin, &(30 * DAY));
+
+    let user = Address::generate(&ctx.env);
+    let metrics = DeveloperMetrics {
+        account_created_at: 1_700_000_000 - 10 * DAY,
+        ..DeveloperMetrics::default()
+    };
+    mint_with_metrics(&ctx, &user, "throwaway", 1500, &metrics);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn test_mint_unknown_account_age_fails_when_gated() {
+    let ctx = setup();
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_700_000_000);
+    ctx.client.set_min_account_age(&ctx.admin, &(30 * DAY));
+
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_min_account_age_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.set_min_account_age(&not_admin, &(30 * DAY));
+}
+
 #[test]
 fn test_nonce_starts_at_zero() {
     let ctx = setup();
@@ -175,6 +297,25 @@ fn test_get_user_token_returns_correct_id() {
     assert_eq!(ctx.client.get_user_token(&user), token_id);
 }
 
+#[test]
+fn test_owner_of_returns_minter() {
+    let ctx = setup();
+    let user_a = Address::generate(&ctx.env);
+    let user_b = Address::generate(&ctx.env);
+    let id_a = mint_for(&ctx, &user_a, "alice", 100);
+    let id_b = mint_for(&ctx, &user_b, "bob", 200);
+
+    assert_eq!(ctx.client.owner_of(&(id_a as u32)), user_a);
+    assert_eq!(ctx.client.owner_of(&(id_b as u32)), user_b);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_owner_of_missing_token_fails() {
+    let ctx = setup();
+    ctx.client.owner_of(&999u32);
+}
+
 #[test]
 fn test_get_token_data_stores_correct_values() {
     let ctx = setup();
@@ -195,210 +336,2448 @@ fn test_get_token_data_missing_token_fails() {
 }
 
 #[test]
-fn test_list_tokens_of_user_with_identity() {
+fn test_identity_never_stale_without_validity_period() {
     let ctx = setup();
     let user = Address::generate(&ctx.env);
-    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
 
-    let tokens = ctx.client.list_tokens_of_user(&user);
-    assert_eq!(tokens.len(), 1);
-    assert_eq!(tokens.get(0), Some(1u64));
+    ctx.env.ledger().with_mut(|li| li.timestamp += 3650 * DAY);
+    assert_eq!(ctx.client.get_validity_period(), 0);
+    assert_eq!(ctx.client.get_status(&token_id), IdentityStatus::Active);
+    assert!(ctx.client.has_fresh_identity(&user));
 }
 
 #[test]
-fn test_list_tokens_of_user_without_identity() {
+fn test_identity_goes_stale_and_update_refreshes_it() {
     let ctx = setup();
+    ctx.client.set_validity_period(&ctx.admin, &(30 * DAY));
     let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
 
-    let tokens = ctx.client.list_tokens_of_user(&user);
-    assert_eq!(tokens.len(), 0);
+    ctx.env.ledger().with_mut(|li| li.timestamp += 30 * DAY - 1);
+    assert_eq!(ctx.client.get_status(&token_id), IdentityStatus::Active);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp += 1);
+    assert_eq!(ctx.client.get_status(&token_id), IdentityStatus::Stale);
+    assert!(ctx.client.has_identity(&user));
+    assert!(!ctx.client.has_fresh_identity(&user));
+
+    update_contributions(&ctx, &user, token_id, 1600);
+    assert_eq!(ctx.client.get_status(&token_id), IdentityStatus::Active);
+    assert!(ctx.client.has_fresh_identity(&user));
 }
 
 #[test]
-fn test_update_token_changes_contributions_and_tier() {
+fn test_has_fresh_identity_without_identity() {
     let ctx = setup();
     let user = Address::generate(&ctx.env);
-    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert!(!ctx.client.has_fresh_identity(&user));
+}
 
-    ctx.client.update_token(
-        &user,
-        &1u64,
-        &String::from_str(&ctx.env, "devfelipenunes"),
-        &3500u32,
-        &Bytes::new(&ctx.env),
-    );
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_get_status_missing_token_fails() {
+    let ctx = setup();
+    ctx.client.get_status(&999u64);
+}
 
-    let data = ctx.client.get_token_data(&1u64);
-    assert_eq!(data.contributions, 3500u32);
-    assert_eq!(data.tier, Tier::Legend);
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_validity_period_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.set_validity_period(&not_admin, &(30 * DAY));
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #13)")]
-fn test_update_token_by_non_owner_fails() {
+fn test_update_cooldown_gates_updates() {
     let ctx = setup();
-    let owner = Address::generate(&ctx.env);
-    let attacker = Address::generate(&ctx.env);
+    ctx.client.set_update_cooldown(&ctx.admin, &DAY);
+    assert_eq!(ctx.client.get_update_cooldown(), DAY);
 
-    mint_for(&ctx, &owner, "owner", 1500);
-    mint_for(&ctx, &attacker, "attacker", 200);
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let minted_at = ctx.env.ledger().timestamp();
+    assert_eq!(
+        ctx.client.next_update_allowed_at(&token_id),
+        minted_at + DAY
+    );
 
-    ctx.client.update_token(
-        &attacker,
-        &1u64,
-        &String::from_str(&ctx.env, "owner"),
-        &3500u32,
-        &Bytes::new(&ctx.env),
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp = minted_at + DAY);
+    update_contributions(&ctx, &user, token_id, 1600);
+    assert_eq!(
+        ctx.client.next_update_allowed_at(&token_id),
+        minted_at + 2 * DAY
     );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_update_token_without_identity_fails() {
+#[should_panic(expected = "Error(Contract, #27)")]
+fn test_update_within_cooldown_fails() {
     let ctx = setup();
+    ctx.client.set_update_cooldown(&ctx.admin, &DAY);
     let user = Address::generate(&ctx.env);
-    ctx.client.update_token(
-        &user,
-        &1u64,
-        &String::from_str(&ctx.env, "ghost"),
-        &100u32,
-        &Bytes::new(&ctx.env),
-    );
-}
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
 
-#[test]
-fn test_tier_boundaries() {
-    assert_eq!(Tier::from_contributions(0), Tier::Novice);
-    assert_eq!(Tier::from_contributions(199), Tier::Novice);
-    assert_eq!(Tier::from_contributions(200), Tier::Pro);
-    assert_eq!(Tier::from_contributions(999), Tier::Pro);
-    assert_eq!(Tier::from_contributions(1000), Tier::Architect);
-    assert_eq!(Tier::from_contributions(2999), Tier::Architect);
-    assert_eq!(Tier::from_contributions(3000), Tier::Legend);
-    assert_eq!(Tier::from_contributions(4999), Tier::Legend);
-    assert_eq!(Tier::from_contributions(5000), Tier::Singularity);
-    assert_eq!(Tier::from_contributions(u32::MAX), Tier::Singularity);
+    ctx.env.ledger().with_mut(|li| li.timestamp += DAY - 1);
+    update_contributions(&ctx, &user, token_id, 1600);
 }
 
 #[test]
-fn test_tier_number() {
-    assert_eq!(Tier::Novice.to_number(), 1);
-    assert_eq!(Tier::Pro.to_number(), 2);
-    assert_eq!(Tier::Architect.to_number(), 3);
-    assert_eq!(Tier::Legend.to_number(), 4);
-    assert_eq!(Tier::Singularity.to_number(), 5);
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_update_cooldown_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.set_update_cooldown(&not_admin, &DAY);
 }
 
 #[test]
-fn test_svg_all_tiers() {
-    let cases: &[(&str, u32, &str)] = &[
-        (
-            "novice_user",
-            50,
-            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#b0c4de'/><text x='50%' y='100' font-size='24' fill='#181c2f' text-anchor='middle'>Novice</text></svg>",
-        ),
-        (
-            "pro_user",
-            500,
-            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#90ee90'/><text x='50%' y='100' font-size='24' fill='#181c2f' text-anchor='middle'>Pro</text></svg>",
-        ),
-        (
-            "arch_user",
-            1500,
-            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#ffd700'/><text x='50%' y='100' font-size='24' fill='#181c2f' text-anchor='middle'>Architect</text></svg>",
-        ),
-        (
-            "legend_user",
-            3500,
-            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#ff8c00'/><text x='50%' y='100' font-size='24' fill='#fff' text-anchor='middle'>Legend</text></svg>",
-        ),
-        (
-            "sing_user",
-            6000,
-            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#8a2be2'/><text x='50%' y='100' font-size='24' fill='#fff' text-anchor='middle'>Singularity</text></svg>",
-        ),
-    ];
+fn test_list_tokens_of_user_with_identity() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
 
-    for (username, contributions, expected_svg) in cases {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, GithubIdentityContract);
-        let client = GithubIdentityContractClient::new(&env, &contract_id);
+    let tokens = ctx.client.list_tokens_of_user(&user);
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens.get(0), Some(1u64));
+}
 
-        let admin = Address::generate(&env);
-        let access_control = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        client.initialize(&admin, &access_control, &treasury, &0);
+#[test]
+fn test_list_tokens_of_user_without_identity() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
 
-        let user = Address::generate(&env);
-        let token_id = client.mint(
-            &user,
-            &stub_signature(&env),
-            &String::from_str(&env, username),
-            contributions,
-            &Bytes::new(&env),
-            &None,
-            &0u64,
-        );
+    let tokens = ctx.client.list_tokens_of_user(&user);
+    assert_eq!(tokens.len(), 0);
+}
 
-        let svg = client.get_token_svg(&token_id);
-        assert_eq!(svg, String::from_str(&env, expected_svg));
+fn language_shares(env: &Env, shares: &[(&str, u32)]) -> Vec<LanguageShare> {
+    let mut languages = Vec::new(env);
+    for (name, share_bps) in shares {
+        languages.push_back(LanguageShare {
+            name: String::from_str(env, name),
+            share_bps: *share_bps,
+        });
     }
+    languages
+}
+
+fn mint_with_languages(ctx: &TestEnv, user: &Address, languages: &Vec<LanguageShare>) -> u64 {
+    ctx.client.mint(
+        user,
+        &stub_signature(&ctx.env),
+        &String::from_str(&ctx.env, "devfelipenunes"),
+        &1500u32,
+        &DeveloperMetrics::default(),
+        languages,
+        &Bytes::new(&ctx.env),
+        &None,
+        &ctx.client.get_nonce(user),
+    )
 }
 
 #[test]
-fn test_svg_architect_exact_output() {
+fn test_languages_stored_on_mint_and_replaced_on_update() {
     let ctx = setup();
     let user = Address::generate(&ctx.env);
-    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let languages = language_shares(&ctx.env, &[("Rust", 6000), ("TypeScript", 2500)]);
+    let token_id = mint_with_languages(&ctx, &user, &languages);
+    assert_eq!(ctx.client.get_languages(&token_id), languages);
 
-    let svg = ctx.client.get_token_svg(&1u64);
-    let expected = String::from_str(
-        &ctx.env,
-        "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'>\
-<rect width='100%' height='100%' fill='#ffd700'/>\
-<text x='50%' y='100' font-size='24' fill='#181c2f' text-anchor='middle'>Architect</text></svg>",
+    let languages = language_shares(&ctx.env, &[("Go", 5000), ("Rust", 5000)]);
+    ctx.client.update_token(
+        &user,
+        &token_id,
+        &String::from_str(&ctx.env, "devfelipenunes"),
+        &1500u32,
+        &DeveloperMetrics::default(),
+        &languages,
+        &Bytes::new(&ctx.env),
     );
-    assert_eq!(svg, expected);
+    assert_eq!(ctx.client.get_languages(&token_id), languages);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #11)")]
-fn test_svg_missing_token_fails() {
-    let ctx = setup();
-    ctx.client.get_token_svg(&999u64);
+fn test_invalid_language_breakdowns_are_rejected() {
+    let cases: [&[(&str, u32)]; 5] = [
+        &[("Rust", 6000), ("Go", 4001)],
+        &[("Rust", 2000), ("Go", 3000)],
+        &[("Rust", 3000), ("Rust", 2000)],
+        &[("Rust", 3000), ("", 2000)],
+        &[("Rust", 3000), ("Go", 0)],
+    ];
+    let env = Env::default();
+    for shares in cases {
+        assert_eq!(
+            types::validate_languages(&language_shares(&env, shares)),
+            Err(Error::InvalidLanguages)
+        );
+    }
+
+    let mut languages = Vec::new(&env);
+    for i in 0..=types::MAX_LANGUAGES {
+        languages.push_back(LanguageShare {
+            name: String::from_bytes(&env, &[b'a' + i as u8]),
+            share_bps: 100,
+        });
+    }
+    assert_eq!(
+        types::validate_languages(&languages),
+        Err(Error::InvalidLanguages)
+    );
+    languages.pop_back();
+    assert_eq!(types::validate_languages(&languages), Ok(()));
 }
 
 #[test]
-fn test_set_mint_fee_by_admin() {
+#[should_panic(expected = "Error(Contract, #34)")]
+fn test_mint_with_languages_over_full_share_fails() {
     let ctx = setup();
-    assert_eq!(ctx.client.get_mint_fee(), 0);
-    ctx.client.set_mint_fee(&ctx.admin, &5_000_000i128);
-    assert_eq!(ctx.client.get_mint_fee(), 5_000_000i128);
+    let user = Address::generate(&ctx.env);
+    let languages = language_shares(&ctx.env, &[("Rust", 9000), ("Go", 1001)]);
+    mint_with_languages(&ctx, &user, &languages);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #10)")]
-fn test_set_mint_fee_by_non_admin_fails() {
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_get_languages_missing_token_fails() {
     let ctx = setup();
-    let not_admin = Address::generate(&ctx.env);
-    ctx.client.set_mint_fee(&not_admin, &5_000_000i128);
+    ctx.client.get_languages(&999u64);
 }
 
 #[test]
-fn test_set_access_control_by_admin() {
+fn test_reputation_score_pinned_examples() {
     let ctx = setup();
-    let new_ac = Address::generate(&ctx.env);
-    ctx.client.set_access_control(&ctx.admin, &new_ac);
+    let architect = Address::generate(&ctx.env);
+    let singularity = Address::generate(&ctx.env);
+    mint_for(&ctx, &architect, "architect", 1500);
+    mint_for(&ctx, &singularity, "singularity", 6000);
+
+    // Architect (2/4 of 250) + score 1500/5000 of 250 + no age + fresh (150).
+    assert_eq!(ctx.client.reputation_score(&architect), 350);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp += 365 * DAY);
+    assert_eq!(ctx.client.reputation_score(&architect), 500);
+    // Everything but the repayment and vouch points, which need reports and vouches.
+    assert_eq!(ctx.client.reputation_score(&singularity), 800);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #10)")]
-fn test_set_access_control_by_non_admin_fails() {
+fn test_reputation_score_of_stale_novice() {
     let ctx = setup();
-    let not_admin = Address::generate(&ctx.env);
-    let new_ac = Address::generate(&ctx.env);
-    ctx.client.set_access_control(&not_admin, &new_ac);
+    ctx.client.set_validity_period(&ctx.admin, &(30 * DAY));
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "novice", 50);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp += 180 * DAY);
+    // Novice (0) + score 50/5000 of 250 (2) + age 180/365 of 150 (73) + stale (0).
+    assert_eq!(ctx.client.reputation_score(&user), 75);
+}
+
+#[test]
+fn test_reputation_score_with_admin_weights() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "legend", 3500);
+
+    let weights = ReputationWeights {
+        tier: 1000,
+        developer_score: 0,
+        identity_age: 0,
+        freshness: 0,
+        repayments: 0,
+        vouches: 0,
+        ..ReputationWeights::default()
+    };
+    ctx.client.set_reputation_weights(&ctx.admin, &weights);
+    assert_eq!(ctx.client.get_reputation_weights(), weights);
+    assert_eq!(ctx.client.reputation_score(&user), 750);
+}
+
+#[test]
+fn test_reputation_score_without_identity() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    assert_eq!(ctx.client.reputation_score(&user), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_set_reputation_weights_not_summing_to_max_fails() {
+    let ctx = setup();
+    ctx.client.set_reputation_weights(
+        &ctx.admin,
+        &ReputationWeights {
+            tier: 250,
+            developer_score: 250,
+            identity_age: 150,
+            freshness: 149,
+            ..ReputationWeights::default()
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_reputation_weights_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client
+        .set_reputation_weights(&not_admin, &ReputationWeights::default());
+}
+
+fn add_reporter(ctx: &TestEnv) -> Address {
+    let reporter = Address::generate(&ctx.env);
+    ctx.client.add_reporter(&ctx.admin, &reporter);
+    reporter
+}
+
+#[test]
+fn test_report_event_updates_summary_and_emits_event() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    assert!(ctx.client.is_reporter(&reporter));
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Repaid, &500);
+    ctx.env.ledger().with_mut(|li| li.timestamp += DAY);
+    let before = ctx.env.events().all().len();
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Late, &200);
+    assert_eq!(ctx.env.events().all().len(), before + 1);
+
+    let (contract, topics, _) = ctx.env.events().all().last().unwrap();
+    assert_eq!(contract, ctx.client.address);
+    assert_eq!(
+        topics,
+        (Symbol::new(&ctx.env, "credit_reported"), token_id).into_val(&ctx.env)
+    );
+
+    let summary = ctx.client.get_credit_summary(&token_id);
+    assert_eq!(
+        summary,
+        CreditSummary {
+            repaid_count: 1,
+            repaid_amount: 500,
+            late_count: 1,
+            late_amount: 200,
+            defaulted_count: 0,
+            defaulted_amount: 0,
+            last_reported_at: ctx.env.ledger().timestamp(),
+        }
+    );
+}
+
+#[test]
+fn test_credit_events_move_reputation() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert_eq!(ctx.client.reputation_score(&user), 350);
+
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Late, &100);
+    assert_eq!(ctx.client.reputation_score(&user), 300);
+
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Defaulted, &1_000);
+    assert_eq!(ctx.client.reputation_score(&user), 50);
+
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Defaulted, &1_000);
+    assert_eq!(ctx.client.reputation_score(&user), 0);
+}
+
+#[test]
+fn test_repayments_earn_reputation() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert_eq!(ctx.client.reputation_score(&user), 350);
+
+    // 5 of REPAYMENT_SATURATION (10) repayments earn half of the 100 repayment points.
+    for _ in 0..5 {
+        ctx.client
+            .report_event(&reporter, &user, &CreditEventKind::Repaid, &100);
+    }
+    assert_eq!(ctx.client.reputation_score(&user), 400);
+
+    for _ in 0..10 {
+        ctx.client
+            .report_event(&reporter, &user, &CreditEventKind::Repaid, &100);
+    }
+    assert_eq!(ctx.client.reputation_score(&user), 450);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_report_event_by_unlisted_reporter_fails() {
+    let ctx = setup();
+    let reporter = Address::generate(&ctx.env);
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Repaid, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_report_event_by_removed_reporter_fails() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    ctx.client.remove_reporter(&ctx.admin, &reporter);
+    assert!(!ctx.client.is_reporter(&reporter));
+
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Repaid, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #37)")]
+fn test_report_event_with_negative_amount_fails() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Late, &-1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_report_event_without_identity_fails() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    let user = Address::generate(&ctx.env);
+    ctx.client
+        .report_event(&reporter, &user, &CreditEventKind::Defaulted, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_add_reporter_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.add_reporter(&not_admin, &not_admin);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_set_reputation_weights_with_oversized_penalty_fails() {
+    let ctx = setup();
+    ctx.client.set_reputation_weights(
+        &ctx.admin,
+        &ReputationWeights {
+            default_penalty: types::MAX_REPUTATION + 1,
+            ..ReputationWeights::default()
+        },
+    );
+}
+
+fn vouch_for(ctx: &TestEnv, voucher: &Address, vouchee_token: u64, weight: u32) {
+    ctx.client.vouch(voucher, &vouchee_token, &weight, &0);
+}
+
+#[test]
+fn test_vouch_is_recorded_and_counted_in_reputation() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    let newcomer = Address::generate(&ctx.env);
+    let voucher_token = mint_for(&ctx, &voucher, "mentor", 500);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 1500);
+    assert_eq!(ctx.client.reputation_score(&newcomer), 350);
+
+    vouch_for(&ctx, &voucher, newcomer_token, 100);
+
+    let vouches = ctx.client.get_vouches(&newcomer_token);
+    assert_eq!(vouches.len(), 1);
+    let vouch = vouches.get_unchecked(0);
+    assert_eq!(vouch.voucher, voucher_token);
+    assert_eq!(vouch.weight, 100);
+    assert_eq!(vouch.stake, 0);
+    assert_eq!(vouch.stake_token, None);
+    // 100 of VOUCH_SATURATION (500) weight earns a fifth of the 100 vouch points.
+    assert_eq!(ctx.client.reputation_score(&newcomer), 370);
+
+    ctx.client.revoke_vouch(&voucher, &newcomer_token);
+    assert_eq!(ctx.client.get_vouches(&newcomer_token).len(), 0);
+    assert_eq!(ctx.client.reputation_score(&newcomer), 350);
+}
+
+#[test]
+fn test_staked_vouch_is_held_and_refunded_on_revoke() {
+    let ctx = setup();
+    let stake_token = ctx
+        .env
+        .register_stellar_asset_contract_v2(ctx.admin.clone())
+        .address();
+    ctx.client.set_stake_token(&ctx.admin, &stake_token);
+    assert_eq!(ctx.client.get_stake_token(), Some(stake_token.clone()));
+
+    let voucher = Address::generate(&ctx.env);
+    let newcomer = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "mentor", 500);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+    token::StellarAssetClient::new(&ctx.env, &stake_token).mint(&voucher, &1_000);
+    let balances = token::TokenClient::new(&ctx.env, &stake_token);
+
+    ctx.client.vouch(&voucher, &newcomer_token, &50u32, &400);
+    assert_eq!(balances.balance(&voucher), 600);
+    assert_eq!(balances.balance(&ctx.client.address), 400);
+    let vouch = ctx.client.get_vouches(&newcomer_token).get_unchecked(0);
+    assert_eq!(vouch.stake_token, Some(stake_token));
+
+    ctx.client.revoke_vouch(&voucher, &newcomer_token);
+    assert_eq!(balances.balance(&voucher), 1_000);
+    assert_eq!(balances.balance(&ctx.client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #35)")]
+fn test_vouch_by_novice_fails() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    let newcomer = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "novice", 50);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+    vouch_for(&ctx, &voucher, newcomer_token, 10);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #38)")]
+fn test_vouch_for_self_fails() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    let voucher_token = mint_for(&ctx, &voucher, "mentor", 500);
+    vouch_for(&ctx, &voucher, voucher_token, 10);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #38)")]
+fn test_vouch_with_oversized_weight_fails() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    let newcomer = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "mentor", 500);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+    vouch_for(&ctx, &voucher, newcomer_token, types::MAX_VOUCH_WEIGHT + 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #40)")]
+fn test_vouch_twice_fails() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    let newcomer = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "mentor", 500);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+    vouch_for(&ctx, &voucher, newcomer_token, 10);
+    vouch_for(&ctx, &voucher, newcomer_token, 10);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #39)")]
+fn test_vouch_beyond_voucher_cap_fails() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "mentor", 500);
+    for _ in 0..=types::MAX_VOUCHES_PER_VOUCHER {
+        let newcomer = Address::generate(&ctx.env);
+        let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+        vouch_for(&ctx, &voucher, newcomer_token, 10);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #39)")]
+fn test_vouch_beyond_vouchee_cap_fails() {
+    let ctx = setup();
+    let newcomer = Address::generate(&ctx.env);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+    for _ in 0..types::MAX_VOUCHES_RECEIVED {
+        let voucher = Address::generate(&ctx.env);
+        mint_for(&ctx, &voucher, "mentor", 500);
+        vouch_for(&ctx, &voucher, newcomer_token, 1);
+    }
+    assert_eq!(
+        ctx.client.get_vouches(&newcomer_token).len(),
+        types::MAX_VOUCHES_RECEIVED
+    );
+
+    let voucher = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "mentor", 500);
+    vouch_for(&ctx, &voucher, newcomer_token, 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #37)")]
+fn test_staked_vouch_without_stake_token_fails() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    let newcomer = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "mentor", 500);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+    ctx.client.vouch(&voucher, &newcomer_token, &10u32, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_revoke_missing_vouch_fails() {
+    let ctx = setup();
+    let voucher = Address::generate(&ctx.env);
+    let newcomer = Address::generate(&ctx.env);
+    mint_for(&ctx, &voucher, "mentor", 500);
+    let newcomer_token = mint_for(&ctx, &newcomer, "newcomer", 50);
+    ctx.client.revoke_vouch(&voucher, &newcomer_token);
+}
+
+fn flag_pair(ctx: &TestEnv, caller: &Address, first: u64, second: u64) -> u64 {
+    ctx.client.flag_cluster(
+        caller,
+        &Vec::from_array(&ctx.env, [first, second]),
+        &String::from_str(&ctx.env, "shared funding wallet"),
+    )
+}
+
+#[test]
+fn test_flag_cluster_excludes_identities_from_unflagged_checks() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    let user_a = Address::generate(&ctx.env);
+    let user_b = Address::generate(&ctx.env);
+    let id_a = mint_for(&ctx, &user_a, "alice", 6000);
+    let id_b = mint_for(&ctx, &user_b, "bob", 1500);
+    assert_eq!(ctx.client.reputation_score(&user_a), 650);
+
+    let cluster_id = flag_pair(&ctx, &reporter, id_a, id_b);
+
+    let cluster = ctx.client.get_cluster(&id_b).unwrap();
+    assert_eq!(cluster.id, cluster_id);
+    assert_eq!(cluster.token_ids, Vec::from_array(&ctx.env, [id_a, id_b]));
+    assert_eq!(cluster.flagged_by, reporter);
+    assert!(ctx.client.meets_tier(&user_a, &Tier::Architect));
+    assert!(!ctx.client.meets_tier_unflagged(&user_a, &Tier::Architect));
+    assert_eq!(ctx.client.reputation_score(&user_a), 150);
+
+    ctx.client.clear_cluster(&ctx.admin, &cluster_id);
+    assert_eq!(ctx.client.get_cluster(&id_a), None);
+    assert!(ctx.client.meets_tier_unflagged(&user_a, &Tier::Architect));
+    assert_eq!(ctx.client.reputation_score(&user_a), 650);
+}
+
+#[test]
+fn test_add_to_cluster_by_admin() {
+    let ctx = setup();
+    let users: [Address; 3] = core::array::from_fn(|_| Address::generate(&ctx.env));
+    let ids: [u64; 3] = core::array::from_fn(|i| mint_for(&ctx, &users[i], "sock", 50));
+    let cluster_id = flag_pair(&ctx, &ctx.admin, ids[0], ids[1]);
+
+    ctx.client.add_to_cluster(
+        &ctx.admin,
+        &cluster_id,
+        &Vec::from_array(&ctx.env, [ids[2]]),
+    );
+    assert_eq!(
+        ctx.client.get_cluster(&ids[2]).unwrap().token_ids,
+        Vec::from_array(&ctx.env, ids)
+    );
+}
+
+fn mint_socks(ctx: &TestEnv, count: u32) -> Vec<u64> {
+    let mut token_ids = Vec::new(&ctx.env);
+    for _ in 0..count {
+        let user = Address::generate(&ctx.env);
+        token_ids.push_back(mint_for(ctx, &user, "sock", 50));
+    }
+    token_ids
+}
+
+#[test]
+fn test_full_cluster_link_and_clear_fit_write_limit() {
+    let ctx = setup();
+    let token_ids = mint_socks(&ctx, types::MAX_CLUSTER_SIZE);
+    let reason = String::from_str(&ctx.env, "shared funding wallet");
+
+    let first = token_ids.slice(0..types::MAX_CLUSTER_LINK_BATCH);
+    let mut cluster_id = 0;
+    let writes = written_entries(&ctx.env, || {
+        cluster_id = ctx.client.flag_cluster(&ctx.admin, &first, &reason);
+    });
+    // A link per token, the cluster, its counter and the admin's auth nonce.
+    assert_eq!(writes, types::MAX_CLUSTER_LINK_BATCH + 3);
+    assert!(writes <= types::TX_MAX_WRITE_ENTRIES);
+
+    let rest = token_ids.slice(types::MAX_CLUSTER_LINK_BATCH..);
+    ctx.client.add_to_cluster(&ctx.admin, &cluster_id, &rest);
+
+    let writes = written_entries(&ctx.env, || {
+        ctx.client.clear_cluster(&ctx.admin, &cluster_id);
+    });
+    assert_eq!(writes, types::MAX_CLUSTER_SIZE + 2);
+    assert!(writes <= types::TX_MAX_WRITE_ENTRIES);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_flag_cluster_above_link_batch_fails() {
+    let ctx = setup();
+    let token_ids = mint_socks(&ctx, types::MAX_CLUSTER_LINK_BATCH + 1);
+    ctx.client.flag_cluster(
+        &ctx.admin,
+        &token_ids,
+        &String::from_str(&ctx.env, "shared funding wallet"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_add_to_full_cluster_fails() {
+    let ctx = setup();
+    let token_ids = mint_socks(&ctx, types::MAX_CLUSTER_SIZE + 1);
+    let cluster_id = ctx.client.flag_cluster(
+        &ctx.admin,
+        &token_ids.slice(0..types::MAX_CLUSTER_LINK_BATCH),
+        &String::from_str(&ctx.env, "shared funding wallet"),
+    );
+    ctx.client.add_to_cluster(
+        &ctx.admin,
+        &cluster_id,
+        &token_ids.slice(types::MAX_CLUSTER_LINK_BATCH..),
+    );
+}
+
+#[test]
+fn test_get_cluster_of_unflagged_token() {
+    let ctx = setup();
+    assert_eq!(ctx.client.get_cluster(&1u64), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #45)")]
+fn test_require_min_tier_unflagged_rejects_flagged_identity() {
+    let ctx = setup();
+    let user_a = Address::generate(&ctx.env);
+    let user_b = Address::generate(&ctx.env);
+    let id_a = mint_for(&ctx, &user_a, "alice", 1500);
+    let id_b = mint_for(&ctx, &user_b, "bob", 1500);
+    flag_pair(&ctx, &ctx.admin, id_a, id_b);
+
+    ctx.client
+        .require_min_tier_unflagged(&user_a, &Tier::Novice);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")]
+fn test_flag_cluster_of_one_token_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "alice", 1500);
+    ctx.client.flag_cluster(
+        &ctx.admin,
+        &Vec::from_array(&ctx.env, [token_id]),
+        &String::from_str(&ctx.env, "shared funding wallet"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")]
+fn test_flag_cluster_without_reason_fails() {
+    let ctx = setup();
+    let user_a = Address::generate(&ctx.env);
+    let user_b = Address::generate(&ctx.env);
+    let id_a = mint_for(&ctx, &user_a, "alice", 1500);
+    let id_b = mint_for(&ctx, &user_b, "bob", 1500);
+    ctx.client.flag_cluster(
+        &ctx.admin,
+        &Vec::from_array(&ctx.env, [id_a, id_b]),
+        &String::from_str(&ctx.env, ""),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #43)")]
+fn test_flag_token_in_two_clusters_fails() {
+    let ctx = setup();
+    let users: [Address; 3] = core::array::from_fn(|_| Address::generate(&ctx.env));
+    let ids: [u64; 3] = core::array::from_fn(|i| mint_for(&ctx, &users[i], "sock", 50));
+    flag_pair(&ctx, &ctx.admin, ids[0], ids[1]);
+    flag_pair(&ctx, &ctx.admin, ids[1], ids[2]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #44)")]
+fn test_add_to_missing_cluster_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "alice", 1500);
+    ctx.client
+        .add_to_cluster(&ctx.admin, &7u64, &Vec::from_array(&ctx.env, [token_id]));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_flag_cluster_by_outsider_fails() {
+    let ctx = setup();
+    let outsider = Address::generate(&ctx.env);
+    flag_pair(&ctx, &outsider, 1, 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_clear_cluster_by_reporter_fails() {
+    let ctx = setup();
+    let reporter = add_reporter(&ctx);
+    let user_a = Address::generate(&ctx.env);
+    let user_b = Address::generate(&ctx.env);
+    let id_a = mint_for(&ctx, &user_a, "alice", 1500);
+    let id_b = mint_for(&ctx, &user_b, "bob", 1500);
+    let cluster_id = flag_pair(&ctx, &reporter, id_a, id_b);
+    ctx.client.clear_cluster(&reporter, &cluster_id);
+}
+
+mod lending_pool {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    use crate::gate::GithubIdentityGateClient;
+    use crate::Tier;
+
+    #[contract]
+    pub struct LendingPool;
+
+    #[contractimpl]
+    impl LendingPool {
+        pub fn borrow(env: Env, identity: Address, borrower: Address, amount: i128) -> i128 {
+            borrower.require_auth();
+            GithubIdentityGateClient::new(&env, &identity)
+                .require_min_tier(&borrower, &Tier::Architect);
+            amount
+        }
+    }
+}
+
+fn lending_pool(ctx: &TestEnv) -> lending_pool::LendingPoolClient<'_> {
+    let pool_id = ctx.env.register_contract(None, lending_pool::LendingPool);
+    lending_pool::LendingPoolClient::new(&ctx.env, &pool_id)
+}
+
+#[test]
+fn test_meets_tier() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    assert!(!ctx.client.meets_tier(&user, &Tier::Novice));
+
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert!(ctx.client.meets_tier(&user, &Tier::Novice));
+    assert!(ctx.client.meets_tier(&user, &Tier::Architect));
+    assert!(!ctx.client.meets_tier(&user, &Tier::Legend));
+}
+
+#[test]
+fn test_consumer_contract_admits_tier_at_threshold() {
+    let ctx = setup();
+    let pool = lending_pool(&ctx);
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    assert_eq!(pool.borrow(&ctx.client.address, &user, &1_000), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #35)")]
+fn test_consumer_contract_rejects_lower_tier() {
+    let ctx = setup();
+    let pool = lending_pool(&ctx);
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 500);
+
+    pool.borrow(&ctx.client.address, &user, &1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_consumer_contract_rejects_missing_identity() {
+    let ctx = setup();
+    let pool = lending_pool(&ctx);
+    let user = Address::generate(&ctx.env);
+
+    pool.borrow(&ctx.client.address, &user, &1_000);
+}
+
+#[test]
+fn test_update_token_changes_contributions_and_tier() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client.update_token(
+        &user,
+        &1u64,
+        &String::from_str(&ctx.env, "devfelipenunes"),
+        &3500u32,
+        &DeveloperMetrics::default(),
+        &Vec::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+    );
+
+    let data = ctx.client.get_token_data(&1u64);
+    assert_eq!(data.contributions, 3500u32);
+    assert_eq!(data.tier, Tier::Legend);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_update_token_by_non_owner_fails() {
+    let ctx = setup();
+    let owner = Address::generate(&ctx.env);
+    let attacker = Address::generate(&ctx.env);
+
+    mint_for(&ctx, &owner, "owner", 1500);
+    mint_for(&ctx, &attacker, "attacker", 200);
+
+    ctx.client.update_token(
+        &attacker,
+        &1u64,
+        &String::from_str(&ctx.env, "owner"),
+        &3500u32,
+        &DeveloperMetrics::default(),
+        &Vec::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_update_token_without_identity_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    ctx.client.update_token(
+        &user,
+        &1u64,
+        &String::from_str(&ctx.env, "ghost"),
+        &100u32,
+        &DeveloperMetrics::default(),
+        &Vec::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+    );
+}
+
+#[test]
+fn test_history_records_mint_and_updates() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 150);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 2_000);
+    update_contributions(&ctx, &user, token_id, 1500);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 3_000);
+    update_contributions(&ctx, &user, token_id, 3500);
+
+    let history = ctx.client.get_history(&token_id, &0, &10);
+    assert_eq!(ctx.client.get_history_len(&token_id), 3);
+    assert_eq!(
+        history,
+        Vec::from_array(
+            &ctx.env,
+            [
+                HistoryEntry {
+                    timestamp: 1_000,
+                    contributions: 150,
+                    tier: Tier::Novice,
+                },
+                HistoryEntry {
+                    timestamp: 2_000,
+                    contributions: 1500,
+                    tier: Tier::Architect,
+                },
+                HistoryEntry {
+                    timestamp: 3_000,
+                    contributions: 3500,
+                    tier: Tier::Legend,
+                },
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_history_pagination() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 100);
+    update_contributions(&ctx, &user, token_id, 200);
+    update_contributions(&ctx, &user, token_id, 300);
+
+    let page = ctx.client.get_history(&token_id, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().contributions, 200);
+
+    assert_eq!(ctx.client.get_history(&token_id, &2, &10).len(), 1);
+    assert_eq!(ctx.client.get_history(&token_id, &5, &10).len(), 0);
+}
+
+#[test]
+fn test_history_is_bounded() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 0);
+    for contributions in 1..=types::MAX_HISTORY_ENTRIES + 4 {
+        update_contributions(&ctx, &user, token_id, contributions);
+    }
+
+    assert_eq!(
+        ctx.client.get_history_len(&token_id),
+        types::MAX_HISTORY_ENTRIES
+    );
+    let oldest = ctx.client.get_history(&token_id, &0, &1).get(0).unwrap();
+    assert_eq!(oldest.contributions, 5);
+
+    let page = ctx
+        .client
+        .get_history(&token_id, &0, &types::MAX_HISTORY_ENTRIES);
+    assert_eq!(page.len(), types::MAX_HISTORY_PAGE);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_history_missing_token_fails() {
+    let ctx = setup();
+    ctx.client.get_history(&999u64, &0, &10);
+}
+
+#[test]
+fn test_activity_score_decay_curve() {
+    let half_life = types::ACTIVITY_HALF_LIFE_SECONDS;
+    assert_eq!(types::activity_score(1000, 0, 0), 1000);
+    assert_eq!(types::activity_score(1000, 0, half_life / 2), 750);
+    assert_eq!(types::activity_score(1000, 0, half_life), 500);
+    assert_eq!(types::activity_score(1000, 0, half_life * 3 / 2), 375);
+    assert_eq!(types::activity_score(1000, 0, half_life * 2), 250);
+    assert_eq!(types::activity_score(1000, 200, half_life), 600);
+    assert_eq!(types::activity_score(u32::MAX, 0, half_life * 40), 0);
+}
+
+#[test]
+fn test_activity_score_decays_with_update_age() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1000);
+    assert_eq!(ctx.client.activity_score(&token_id), 1000);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::ACTIVITY_HALF_LIFE_SECONDS);
+    assert_eq!(ctx.client.activity_score(&token_id), 500);
+
+    update_contributions(&ctx, &user, token_id, 1000);
+    assert_eq!(ctx.client.activity_score(&token_id), 1000);
+}
+
+#[test]
+fn test_activity_score_rewards_recent_growth() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_700_000_000);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1000);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp += 30 * DAY);
+    update_contributions(&ctx, &user, token_id, 1600);
+    assert_eq!(ctx.client.activity_score(&token_id), 2200);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::ACTIVITY_WINDOW_SECONDS);
+    update_contributions(&ctx, &user, token_id, 1600);
+    assert_eq!(ctx.client.activity_score(&token_id), 1600);
+}
+
+#[test]
+fn test_activity_tiering_lets_stale_identities_drift_down() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::ACTIVITY_HALF_LIFE_SECONDS);
+    assert_eq!(ctx.client.recompute_tier(&token_id), Tier::Architect);
+
+    ctx.client.set_activity_tiering(&ctx.admin, &true);
+    assert!(ctx.client.get_activity_tiering());
+    assert_eq!(ctx.client.recompute_tier(&token_id), Tier::Pro);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_activity_tiering_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.set_activity_tiering(&not_admin, &true);
+}
+
+#[test]
+fn test_tier_boundaries() {
+    let env = Env::default();
+    let thresholds = Vec::from_array(&env, types::DEFAULT_TIER_THRESHOLDS);
+    let tier = |score: u32| Tier::from_thresholds(score, &thresholds);
+    assert_eq!(tier(0), Tier::Novice);
+    assert_eq!(tier(199), Tier::Novice);
+    assert_eq!(tier(200), Tier::Pro);
+    assert_eq!(tier(999), Tier::Pro);
+    assert_eq!(tier(1000), Tier::Architect);
+    assert_eq!(tier(2999), Tier::Architect);
+    assert_eq!(tier(3000), Tier::Legend);
+    assert_eq!(tier(4999), Tier::Legend);
+    assert_eq!(tier(5000), Tier::Singularity);
+    assert_eq!(tier(u32::MAX), Tier::Singularity);
+}
+
+#[test]
+fn test_extended_thresholds_unlock_additional_tiers() {
+    let ctx = setup();
+    let thresholds = Vec::from_array(&ctx.env, [200u32, 1000, 3000, 5000, 8000, 12000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+    assert_eq!(ctx.client.get_tier_thresholds(), thresholds);
+
+    let singularity = Address::generate(&ctx.env);
+    let mythic = Address::generate(&ctx.env);
+    let transcendent = Address::generate(&ctx.env);
+    let singularity_id = mint_for(&ctx, &singularity, "singularity", 6000);
+    let mythic_id = mint_for(&ctx, &mythic, "mythic", 9000);
+    let transcendent_id = mint_for(&ctx, &transcendent, "transcendent", 12000);
+    assert_eq!(
+        ctx.client.get_token_data(&singularity_id).tier,
+        Tier::Singularity
+    );
+    assert_eq!(ctx.client.get_token_data(&mythic_id).tier, Tier::Mythic);
+    assert_eq!(
+        ctx.client.get_token_data(&transcendent_id).tier,
+        Tier::Transcendent
+    );
+    assert!(ctx.client.meets_tier(&mythic, &Tier::Singularity));
+    assert!(!ctx.client.meets_tier(&mythic, &Tier::Transcendent));
+
+    // The top tier of the active table earns the full tier weight: Singularity is now
+    // 4/6 of 250, and the score ceiling is the Transcendent threshold.
+    assert_eq!(ctx.client.reputation_score(&transcendent), 650);
+    assert_eq!(ctx.client.reputation_score(&singularity), 441);
+
+    // Going back to the default table re-tiers the additional tiers to Singularity.
+    let defaults = Vec::from_array(&ctx.env, types::DEFAULT_TIER_THRESHOLDS);
+    ctx.client.set_tier_thresholds(&ctx.admin, &defaults);
+    assert_eq!(
+        ctx.client.recompute_tier(&transcendent_id),
+        Tier::Singularity
+    );
+}
+
+#[test]
+fn test_default_tier_thresholds() {
+    let ctx = setup();
+    assert_eq!(
+        ctx.client.get_tier_thresholds(),
+        Vec::from_array(&ctx.env, [200u32, 1000, 3000, 5000])
+    );
+}
+
+#[test]
+fn test_set_tier_thresholds_applies_to_mint_and_update() {
+    let ctx = setup();
+    let thresholds = Vec::from_array(&ctx.env, [500u32, 2000, 4000, 8000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+    assert_eq!(ctx.client.get_tier_thresholds(), thresholds);
+
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert_eq!(ctx.client.get_token_data(&1u64).tier, Tier::Pro);
+
+    ctx.client.update_token(
+        &user,
+        &1u64,
+        &String::from_str(&ctx.env, "devfelipenunes"),
+        &6000u32,
+        &DeveloperMetrics::default(),
+        &Vec::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+    );
+    assert_eq!(ctx.client.get_token_data(&1u64).tier, Tier::Legend);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_set_tier_thresholds_not_increasing_fails() {
+    let ctx = setup();
+    let thresholds = Vec::from_array(&ctx.env, [200u32, 1000, 1000, 5000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_set_tier_thresholds_wrong_length_fails() {
+    let ctx = setup();
+    let thresholds = Vec::from_array(&ctx.env, [200u32, 1000, 3000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_set_tier_thresholds_beyond_top_tier_fails() {
+    let ctx = setup();
+    let thresholds = Vec::from_array(&ctx.env, [200u32, 1000, 3000, 5000, 8000, 12000, 20000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_set_tier_thresholds_zero_first_fails() {
+    let ctx = setup();
+    let thresholds = Vec::from_array(&ctx.env, [0u32, 1000, 3000, 5000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_tier_thresholds_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    let thresholds = Vec::from_array(&ctx.env, [500u32, 2000, 4000, 8000]);
+    ctx.client.set_tier_thresholds(&not_admin, &thresholds);
+}
+
+#[test]
+fn test_recompute_tier_follows_new_thresholds() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let updated_at = ctx.client.get_token_data(&token_id).updated_at;
+
+    let thresholds = Vec::from_array(&ctx.env, [100u32, 500, 1200, 4000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+    assert_eq!(ctx.client.get_token_data(&token_id).tier, Tier::Architect);
+
+    ctx.env.ledger().with_mut(|li| li.timestamp += DAY);
+    assert_eq!(ctx.client.recompute_tier(&token_id), Tier::Legend);
+    let data = ctx.client.get_token_data(&token_id);
+    assert_eq!(data.tier, Tier::Legend);
+    assert_eq!(data.updated_at, updated_at);
+
+    // The re-tier is snapshotted at the time it happened.
+    assert_eq!(ctx.client.get_history_len(&token_id), 2);
+    let latest = ctx.client.get_history(&token_id, &1, &1).get_unchecked(0);
+    assert_eq!(latest.tier, Tier::Legend);
+    assert_eq!(latest.contributions, 1500);
+    assert_eq!(latest.timestamp, updated_at + DAY);
+}
+
+#[test]
+fn test_recompute_tier_emits_event_only_on_change() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    let before = ctx.env.events().all().len();
+    ctx.client.recompute_tier(&token_id);
+    assert_eq!(ctx.env.events().all().len(), before);
+    assert_eq!(ctx.client.get_history_len(&token_id), 1);
+
+    let thresholds = Vec::from_array(&ctx.env, [100u32, 500, 1200, 4000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+    let before = ctx.env.events().all().len();
+    ctx.client.recompute_tier(&token_id);
+    assert_eq!(ctx.env.events().all().len(), before + 1);
+}
+
+/// Ledger entries created, changed or removed while running `f`, i.e. the read-write
+/// part of the transaction footprint it would need.
+fn written_entries(env: &Env, f: impl FnOnce()) -> u32 {
+    let before = env.to_snapshot().ledger.ledger_entries;
+    f();
+    let after = env.to_snapshot().ledger.ledger_entries;
+    let written = after
+        .iter()
+        .filter(|(key, (entry, _))| !before.iter().any(|(k, (e, _))| k == key && e == entry))
+        .count();
+    let removed = before
+        .iter()
+        .filter(|(key, _)| !after.iter().any(|(k, _)| k == key))
+        .count();
+    (written + removed) as u32
+}
+
+#[test]
+fn test_recompute_tiers_batch_counts_changes() {
+    let ctx = setup();
+    let user_a = Address::generate(&ctx.env);
+    let user_b = Address::generate(&ctx.env);
+    let user_c = Address::generate(&ctx.env);
+    let id_a = mint_for(&ctx, &user_a, "alice", 150);
+    let id_b = mint_for(&ctx, &user_b, "bob", 1500);
+    let id_c = mint_for(&ctx, &user_c, "carol", 6000);
+
+    let thresholds = Vec::from_array(&ctx.env, [100u32, 500, 1200, 4000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+
+    let changed = ctx
+        .client
+        .recompute_tiers(&ctx.admin, &Vec::from_array(&ctx.env, [id_a, id_b, id_c]));
+    assert_eq!(changed, 2);
+    assert_eq!(ctx.client.get_token_data(&id_a).tier, Tier::Pro);
+    assert_eq!(ctx.client.get_token_data(&id_b).tier, Tier::Legend);
+    assert_eq!(ctx.client.get_token_data(&id_c).tier, Tier::Singularity);
+}
+
+#[test]
+fn test_full_recompute_batch_fits_write_limit() {
+    let ctx = setup();
+    let mut token_ids = Vec::new(&ctx.env);
+    for _ in 0..types::MAX_RECOMPUTE_BATCH {
+        let user = Address::generate(&ctx.env);
+        token_ids.push_back(mint_for(&ctx, &user, "novice", 150));
+    }
+    let thresholds = Vec::from_array(&ctx.env, [100u32, 500, 1200, 4000]);
+    ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+
+    let writes = written_entries(&ctx.env, || {
+        assert_eq!(
+            ctx.client.recompute_tiers(&ctx.admin, &token_ids),
+            types::MAX_RECOMPUTE_BATCH
+        );
+    });
+    // Data and history per token, plus the admin's auth nonce.
+    assert_eq!(writes, 2 * types::MAX_RECOMPUTE_BATCH + 1);
+    assert!(writes <= types::TX_MAX_WRITE_ENTRIES);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_recompute_tiers_batch_too_large_fails() {
+    let ctx = setup();
+    let mut token_ids = Vec::new(&ctx.env);
+    for token_id in 0..=types::MAX_RECOMPUTE_BATCH as u64 {
+        token_ids.push_back(token_id);
+    }
+    ctx.client.recompute_tiers(&ctx.admin, &token_ids);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_recompute_tiers_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.recompute_tiers(&not_admin, &Vec::new(&ctx.env));
+}
+
+#[test]
+fn test_mint_stores_metrics() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_with_metrics(&ctx, &user, "devfelipenunes", 500, &sample_metrics());
+
+    assert_eq!(
+        ctx.client.get_token_data(&token_id).metrics,
+        sample_metrics()
+    );
+}
+
+#[test]
+fn test_default_score_weights_count_contributions_only() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_with_metrics(&ctx, &user, "devfelipenunes", 500, &sample_metrics());
+
+    assert_eq!(ctx.client.get_score_weights(), ScoreWeights::default());
+    assert_eq!(ctx.client.developer_score(&token_id), 500);
+    assert_eq!(ctx.client.get_token_data(&token_id).tier, Tier::Pro);
+}
+
+#[test]
+fn test_weighted_score_drives_tier() {
+    let ctx = setup();
+    let weights = ScoreWeights {
+        contributions: 10_000,
+        merged_prs: 50_000,
+        reviews: 0,
+        repos_owned: 0,
+        stars_received: 5_000,
+        followers: 0,
+        longest_streak: 0,
+    };
+    ctx.client.set_score_weights(&ctx.admin, &weights);
+
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_with_metrics(&ctx, &user, "devfelipenunes", 500, &sample_metrics());
+
+    // 500 + 200 * 5 + 1_000 / 2
+    assert_eq!(ctx.client.developer_score(&token_id), 2_000);
+    assert_eq!(ctx.client.get_token_data(&token_id).tier, Tier::Architect);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_set_score_weights_all_zero_fails() {
+    let ctx = setup();
+    let weights = ScoreWeights {
+        contributions: 0,
+        ..ScoreWeights::default()
+    };
+    ctx.client.set_score_weights(&ctx.admin, &weights);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_set_score_weights_above_max_fails() {
+    let ctx = setup();
+    let weights = ScoreWeights {
+        followers: types::MAX_SCORE_WEIGHT + 1,
+        ..ScoreWeights::default()
+    };
+    ctx.client.set_score_weights(&ctx.admin, &weights);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_score_weights_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client
+        .set_score_weights(&not_admin, &ScoreWeights::default());
+}
+
+#[test]
+fn test_tier_number() {
+    assert_eq!(Tier::Novice.to_number(), 1);
+    assert_eq!(Tier::Pro.to_number(), 2);
+    assert_eq!(Tier::Architect.to_number(), 3);
+    assert_eq!(Tier::Legend.to_number(), 4);
+    assert_eq!(Tier::Singularity.to_number(), 5);
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn test_tier_theme_matches_color_svg_and_metadata() {
+    let tiers = [
+        (Tier::Novice, 50u32),
+        (Tier::Pro, 500),
+        (Tier::Architect, 1500),
+        (Tier::Legend, 3500),
+        (Tier::Singularity, 6000),
+        (Tier::Mythic, 9000),
+        (Tier::Transcendent, 13000),
+    ];
+
+    for (tier, contributions) in tiers {
+        let ctx = setup();
+        let thresholds = Vec::from_array(&ctx.env, [200u32, 1000, 3000, 5000, 8000, 12000]);
+        ctx.client.set_tier_thresholds(&ctx.admin, &thresholds);
+        let user = Address::generate(&ctx.env);
+        let token_id = mint_for(&ctx, &user, "devfelipenunes", contributions);
+        let theme = tier.theme();
+
+        assert_eq!(
+            tier.to_color(&ctx.env),
+            String::from_str(&ctx.env, theme.accent)
+        );
+        assert_eq!(
+            tier.to_string(&ctx.env),
+            String::from_str(&ctx.env, theme.label)
+        );
+
+        let svg = ctx.client.get_token_svg(&token_id);
+        let svg_bytes = types::TextBuffer::<2048>::from_string(&svg);
+        assert!(contains(svg_bytes.as_bytes(), theme.background));
+        assert!(contains(svg_bytes.as_bytes(), theme.foreground));
+        assert!(contains(svg_bytes.as_bytes(), theme.accent));
+        assert!(contains(svg_bytes.as_bytes(), theme.label));
+
+        let data = ctx.client.get_token_data(&token_id);
+        let json = types::metadata_json(token_id, &data, &svg);
+        assert!(contains(json.as_bytes(), theme.accent));
+        assert!(contains(json.as_bytes(), theme.label));
+    }
+}
+
+#[test]
+fn test_svg_all_tiers() {
+    let cases: &[(&str, u32, &str)] = &[
+        (
+            "novice_user",
+            50,
+            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#2b1d0e' stroke='#CD7F32' stroke-width='4'/><text x='50%' y='60' font-size='24' fill='#CD7F32' text-anchor='middle'>Novice</text><text x='50%' y='100' font-size='18' fill='#f5e6d3' text-anchor='middle'>@novice_user</text><text x='50%' y='135' font-size='14' fill='#f5e6d3' text-anchor='middle'>50 contributions</text><text x='50%' y='175' font-size='11' fill='#f5e6d3' text-anchor='middle'>#1 - minted 1970-01-01</text></svg>",
+        ),
+        (
+            "pro_user",
+            500,
+            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#1f2326' stroke='#C0C0C0' stroke-width='4'/><text x='50%' y='60' font-size='24' fill='#C0C0C0' text-anchor='middle'>Pro</text><text x='50%' y='100' font-size='18' fill='#f2f2f2' text-anchor='middle'>@pro_user</text><text x='50%' y='135' font-size='14' fill='#f2f2f2' text-anchor='middle'>500 contributions</text><text x='50%' y='175' font-size='11' fill='#f2f2f2' text-anchor='middle'>#1 - minted 1970-01-01</text></svg>",
+        ),
+        (
+            "arch_user",
+            1500,
+            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#2a2200' stroke='#FFD700' stroke-width='4'/><text x='50%' y='60' font-size='24' fill='#FFD700' text-anchor='middle'>Architect</text><text x='50%' y='100' font-size='18' fill='#fff8dc' text-anchor='middle'>@arch_user</text><text x='50%' y='135' font-size='14' fill='#fff8dc' text-anchor='middle'>1500 contributions</text><text x='50%' y='175' font-size='11' fill='#fff8dc' text-anchor='middle'>#1 - minted 1970-01-01</text></svg>",
+        ),
+        (
+            "legend_user",
+            3500,
+            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#202024' stroke='#E5E4E2' stroke-width='4'/><text x='50%' y='60' font-size='24' fill='#E5E4E2' text-anchor='middle'>Legend</text><text x='50%' y='100' font-size='18' fill='#ffffff' text-anchor='middle'>@legend_user</text><text x='50%' y='135' font-size='14' fill='#ffffff' text-anchor='middle'>3500 contributions</text><text x='50%' y='175' font-size='11' fill='#ffffff' text-anchor='middle'>#1 - minted 1970-01-01</text></svg>",
+        ),
+        (
+            "sing_user",
+            6000,
+            "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#0b1a06' stroke='#39FF14' stroke-width='4'/><text x='50%' y='60' font-size='24' fill='#39FF14' text-anchor='middle'>Singularity</text><text x='50%' y='100' font-size='18' fill='#eaffea' text-anchor='middle'>@sing_user</text><text x='50%' y='135' font-size='14' fill='#eaffea' text-anchor='middle'>6000 contributions</text><text x='50%' y='175' font-size='11' fill='#eaffea' text-anchor='middle'>#1 - minted 1970-01-01</text></svg>",
+        ),
+    ];
+
+    for (username, contributions, expected_svg) in cases {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, GithubIdentityContract);
+        let client = GithubIdentityContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let access_control = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        client.initialize(&admin, &access_control, &treasury, &0);
+
+        let user = Address::generate(&env);
+        let token_id = client.mint(
+            &user,
+            &stub_signature(&env),
+            &String::from_str(&env, username),
+            contributions,
+            &DeveloperMetrics::default(),
+            &Vec::new(&env),
+            &Bytes::new(&env),
+            &None,
+            &0u64,
+        );
+
+        let svg = client.get_token_svg(&token_id);
+        assert_eq!(svg, String::from_str(&env, expected_svg));
+    }
+}
+
+#[test]
+fn test_svg_architect_exact_output() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_700_000_000);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    let svg = ctx.client.get_token_svg(&1u64);
+    let expected = String::from_str(
+        &ctx.env,
+        "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'>\
+<rect width='100%' height='100%' fill='#2a2200' stroke='#FFD700' stroke-width='4'/>\
+<text x='50%' y='60' font-size='24' fill='#FFD700' text-anchor='middle'>Architect</text>\
+<text x='50%' y='100' font-size='18' fill='#fff8dc' text-anchor='middle'>@devfelipenunes</text>\
+<text x='50%' y='135' font-size='14' fill='#fff8dc' text-anchor='middle'>1500 contributions</text>\
+<text x='50%' y='175' font-size='11' fill='#fff8dc' text-anchor='middle'>#1 - minted 2023-11-14</text>\
+</svg>",
+    );
+    assert_eq!(svg, expected);
+}
+
+#[test]
+fn test_svg_escapes_username() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "<a href='x'>&\"", 50);
+
+    let svg = ctx.client.get_token_svg(&1u64);
+    let expected = String::from_str(
+        &ctx.env,
+        "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'>\
+<rect width='100%' height='100%' fill='#2b1d0e' stroke='#CD7F32' stroke-width='4'/>\
+<text x='50%' y='60' font-size='24' fill='#CD7F32' text-anchor='middle'>Novice</text>\
+<text x='50%' y='100' font-size='18' fill='#f5e6d3' text-anchor='middle'>@&lt;a href=&apos;x&apos;&gt;&amp;&quot;</text>\
+<text x='50%' y='135' font-size='14' fill='#f5e6d3' text-anchor='middle'>50 contributions</text>\
+<text x='50%' y='175' font-size='11' fill='#f5e6d3' text-anchor='middle'>#1 - minted 1970-01-01</text>\
+</svg>",
+    );
+    assert_eq!(svg, expected);
+}
+
+#[test]
+fn test_date_formatting() {
+    let cases: &[(u64, &str)] = &[
+        (0, "1970-01-01"),
+        (951_782_400, "2000-02-29"),
+        (1_700_000_000, "2023-11-14"),
+        (4_107_542_399, "2100-02-28"),
+    ];
+
+    for (timestamp, expected) in cases {
+        let mut buffer = types::TextBuffer::<16>::new();
+        buffer.push_date(*timestamp);
+        assert_eq!(buffer.as_bytes(), expected.as_bytes());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_svg_missing_token_fails() {
+    let ctx = setup();
+    ctx.client.get_token_svg(&999u64);
+}
+
+#[test]
+fn test_base64_rfc4648_vectors() {
+    let cases: &[(&str, &str)] = &[
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    for (input, expected) in cases {
+        let mut buffer = types::TextBuffer::<16>::new();
+        buffer.push_base64(input.as_bytes());
+        assert_eq!(buffer.as_bytes(), expected.as_bytes());
+    }
+}
+
+#[test]
+fn test_name_and_symbol() {
+    let ctx = setup();
+    assert_eq!(
+        ctx.client.name(),
+        String::from_str(&ctx.env, "GitHub Identity")
+    );
+    assert_eq!(ctx.client.symbol(), String::from_str(&ctx.env, "GHID"));
+}
+
+#[test]
+fn test_balance_reflects_identity() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+
+    assert_eq!(ctx.client.balance(&user), 0);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert_eq!(ctx.client.balance(&user), 1);
+}
+
+#[test]
+fn test_metadata_json_architect_exact_output() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_700_000_000);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    let data = ctx.client.get_token_data(&1u64);
+    let svg = String::from_str(&ctx.env, "<svg/>");
+    let json = types::metadata_json(1, &data, &svg);
+    let expected = "{\"name\":\"GitHub Identity #1\",\
+\"description\":\"Soulbound identity token attesting GitHub developer activity on Stellar.\",\
+\"image\":\"data:image/svg+xml;base64,PHN2Zy8+\",\
+\"attributes\":[{\"trait_type\":\"tier\",\"value\":\"Architect\"},\
+{\"trait_type\":\"color\",\"value\":\"#FFD700\"},\
+{\"trait_type\":\"contributions\",\"value\":1500},\
+{\"trait_type\":\"minted_at\",\"display_type\":\"date\",\"value\":1700000000},\
+{\"trait_type\":\"updated_at\",\"display_type\":\"date\",\"value\":1700000000}]}";
+    assert_eq!(json.as_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn test_token_uri_architect_exact_output() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    ctx.env.ledger().with_mut(|li| li.timestamp = 1_700_000_000);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    let uri = ctx.client.token_uri(&1u32);
+    let expected = String::from_str(
+        &ctx.env,
+        "data:application/json;base64,\
+eyJuYW1lIjoiR2l0SHViIElkZW50aXR5ICMxIiwiZGVzY3JpcHRpb24iOiJTb3VsYm91bmQgaWRlbnRpdHkgdG9rZW4g\
+YXR0ZXN0aW5nIEdpdEh1YiBkZXZlbG9wZXIgYWN0aXZpdHkgb24gU3RlbGxhci4iLCJpbWFnZSI6ImRhdGE6aW1hZ2Uv\
+c3ZnK3htbDtiYXNlNjQsUEhOMlp5QjRiV3h1Y3owbmFIUjBjRG92TDNkM2R5NTNNeTV2Y21jdk1qQXdNQzl6ZG1jbklI\
+ZHBaSFJvUFNjek5UQW5JR2hsYVdkb2REMG5NakF3Sno0OGNtVmpkQ0IzYVdSMGFEMG5NVEF3SlNjZ2FHVnBaMmgwUFNj\
+eE1EQWxKeUJtYVd4c1BTY2pNbUV5TWpBd0p5QnpkSEp2YTJVOUp5TkdSa1EzTURBbklITjBjbTlyWlMxM2FXUjBhRDBu\
+TkNjdlBqeDBaWGgwSUhnOUp6VXdKU2NnZVQwbk5qQW5JR1p2Ym5RdGMybDZaVDBuTWpRbklHWnBiR3c5SnlOR1JrUTNN\
+REFuSUhSbGVIUXRZVzVqYUc5eVBTZHRhV1JrYkdVblBrRnlZMmhwZEdWamREd3ZkR1Y0ZEQ0OGRHVjRkQ0I0UFNjMU1D\
+VW5JSGs5SnpFd01DY2dabTl1ZEMxemFYcGxQU2N4T0NjZ1ptbHNiRDBuSTJabVpqaGtZeWNnZEdWNGRDMWhibU5vYjNJ\
+OUoyMXBaR1JzWlNjK1FHUmxkbVpsYkdsd1pXNTFibVZ6UEM5MFpYaDBQangwWlhoMElIZzlKelV3SlNjZ2VUMG5NVE0x\
+SnlCbWIyNTBMWE5wZW1VOUp6RTBKeUJtYVd4c1BTY2pabVptT0dSakp5QjBaWGgwTFdGdVkyaHZjajBuYldsa1pHeGxK\
+ejR4TlRBd0lHTnZiblJ5YVdKMWRHbHZibk04TDNSbGVIUStQSFJsZUhRZ2VEMG5OVEFsSnlCNVBTY3hOelVuSUdadmJu\
+UXRjMmw2WlQwbk1URW5JR1pwYkd3OUp5Tm1abVk0WkdNbklIUmxlSFF0WVc1amFHOXlQU2R0YVdSa2JHVW5QaU14SUMw\
+Z2JXbHVkR1ZrSURJd01qTXRNVEV0TVRROEwzUmxlSFErUEM5emRtYysiLCJhdHRyaWJ1dGVzIjpbeyJ0cmFpdF90eXBl\
+IjoidGllciIsInZhbHVlIjoiQXJjaGl0ZWN0In0seyJ0cmFpdF90eXBlIjoiY29sb3IiLCJ2YWx1ZSI6IiNGRkQ3MDAi\
+fSx7InRyYWl0X3R5cGUiOiJjb250cmlidXRpb25zIiwidmFsdWUiOjE1MDB9LHsidHJhaXRfdHlwZSI6Im1pbnRlZF9h\
+dCIsImRpc3BsYXlfdHlwZSI6ImRhdGUiLCJ2YWx1ZSI6MTcwMDAwMDAwMH0seyJ0cmFpdF90eXBlIjoidXBkYXRlZF9h\
+dCIsImRpc3BsYXlfdHlwZSI6ImRhdGUiLCJ2YWx1ZSI6MTcwMDAwMDAwMH1dfQ==",
+    );
+    assert_eq!(uri, expected);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_token_uri_missing_token_fails() {
+    let ctx = setup();
+    ctx.client.token_uri(&999u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_transfer_not_allowed() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let other = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client.transfer(&user, &other, &1u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_transfer_from_not_allowed() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let other = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client.transfer_from(&other, &user, &other, &1u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_approve_not_allowed() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let other = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client.approve(&user, &other, &1u32, &1000u32);
+}
+
+#[test]
+fn test_nft_entry_points_take_sep50_u32_token_ids() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    // Invoke by name with a raw u32 argument, as a SEP-50 wallet would.
+    let owner: Address = ctx.env.invoke_contract(
+        &ctx.client.address,
+        &Symbol::new(&ctx.env, "owner_of"),
+        soroban_sdk::vec![&ctx.env, 1u32.into_val(&ctx.env)],
+    );
+    assert_eq!(owner, user);
+    assert_eq!(ctx.client.get_approved(&1u32), None);
+}
+
+#[test]
+fn test_svg_template_renders_placeholders() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "dev<x>", 1500);
+
+    ctx.client.set_svg_template(
+        &ctx.admin,
+        &Tier::Architect,
+        &String::from_str(
+            &ctx.env,
+            "<svg><text>{tier}:{username}:{contributions}:{token_id}:{unknown}</text></svg>",
+        ),
+    );
+
+    let svg = ctx.client.get_token_svg(&1u64);
+    assert_eq!(
+        svg,
+        String::from_str(
+            &ctx.env,
+            "<svg><text>Architect:dev&lt;x&gt;:1500:1:{unknown}</text></svg>",
+        )
+    );
+}
+
+#[test]
+fn test_svg_template_only_applies_to_its_tier() {
+    let ctx = setup();
+    let novice = Address::generate(&ctx.env);
+    let architect = Address::generate(&ctx.env);
+    let novice_id = mint_for(&ctx, &novice, "novice", 50);
+    let architect_id = mint_for(&ctx, &architect, "architect", 1500);
+
+    let template = String::from_str(&ctx.env, "<svg>{username}</svg>");
+    ctx.client
+        .set_svg_template(&ctx.admin, &Tier::Architect, &template);
+
+    assert_eq!(
+        ctx.client.get_token_svg(&architect_id),
+        String::from_str(&ctx.env, "<svg>architect</svg>")
+    );
+    let novice_data = ctx.client.get_token_data(&novice_id);
+    assert_eq!(
+        ctx.client.get_token_svg(&novice_id),
+        types::generate_svg(&ctx.env, novice_id, &novice_data, &Vec::new(&ctx.env))
+    );
+    assert_eq!(
+        ctx.client.get_svg_template(&Tier::Architect),
+        Some(template)
+    );
+    assert_eq!(ctx.client.get_svg_template(&Tier::Novice), None);
+}
+
+#[test]
+fn test_remove_svg_template_falls_back_to_built_in() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let built_in = ctx.client.get_token_svg(&token_id);
+
+    ctx.client.set_svg_template(
+        &ctx.admin,
+        &Tier::Architect,
+        &String::from_str(&ctx.env, "<svg>{username}</svg>"),
+    );
+    ctx.client.remove_svg_template(&ctx.admin, &Tier::Architect);
+
+    assert_eq!(ctx.client.get_token_svg(&token_id), built_in);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_svg_template_with_too_many_slots_fails() {
+    let ctx = setup();
+    ctx.client.set_svg_template(
+        &ctx.admin,
+        &Tier::Pro,
+        &String::from_str(
+            &ctx.env,
+            "{tier}{tier}{tier}{tier}{tier}{tier}{tier}{tier}{tier}",
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_empty_svg_template_fails() {
+    let ctx = setup();
+    ctx.client
+        .set_svg_template(&ctx.admin, &Tier::Pro, &String::from_str(&ctx.env, ""));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_svg_template_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.set_svg_template(
+        &not_admin,
+        &Tier::Pro,
+        &String::from_str(&ctx.env, "<svg/>"),
+    );
+}
+
+fn define_badge(ctx: &TestEnv, badge_id: u32, name: &str, art: &str) {
+    ctx.client.define_badge(
+        &ctx.admin,
+        &badge_id,
+        &BadgeDefinition {
+            name: String::from_str(&ctx.env, name),
+            art: String::from_str(&ctx.env, art),
+        },
+    );
+}
+
+#[test]
+fn test_award_badge_lists_and_renders_it() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "<circle r='16'/>");
+    define_badge(&ctx, 7, "Streak 100", "<rect width='32'/>");
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert_eq!(ctx.client.list_badges(&token_id), Vec::new(&ctx.env));
+
+    ctx.client.award_badge(&ctx.admin, &token_id, &7u32);
+    ctx.client.award_badge(&ctx.admin, &token_id, &1u32);
+
+    assert_eq!(
+        ctx.client.list_badges(&token_id),
+        Vec::from_array(&ctx.env, [7u32, 1u32])
+    );
+    assert_eq!(
+        ctx.client.get_badge(&1u32).name,
+        String::from_str(&ctx.env, "First PR")
+    );
+    let svg = ctx.client.get_token_svg(&token_id);
+    let svg_bytes = types::TextBuffer::<2048>::from_string(&svg);
+    assert!(contains(
+        svg_bytes.as_bytes(),
+        "</text><g transform='translate(8,8)'><rect width='32'/></g>\
+         <g transform='translate(48,8)'><circle r='16'/></g></svg>"
+    ));
+}
+
+#[test]
+fn test_badges_slot_in_svg_template() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "<circle r='16'/>");
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    ctx.client.award_badge(&ctx.admin, &token_id, &1u32);
+
+    ctx.client.set_svg_template(
+        &ctx.admin,
+        &Tier::Architect,
+        &String::from_str(&ctx.env, "<svg>{badges}</svg>"),
+    );
+    assert_eq!(
+        ctx.client.get_token_svg(&token_id),
+        String::from_str(
+            &ctx.env,
+            "<svg><g transform='translate(8,8)'><circle r='16'/></g></svg>"
+        )
+    );
+}
+
+#[test]
+fn test_token_uri_fits_with_maximum_badges() {
+    let ctx = setup();
+    let art = String::from_bytes(&ctx.env, &[b'x'; types::MAX_BADGE_ART_LEN as usize]);
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&", 1500);
+    for badge_id in 0..types::MAX_BADGES_PER_TOKEN {
+        ctx.client.define_badge(
+            &ctx.admin,
+            &badge_id,
+            &BadgeDefinition {
+                name: String::from_str(&ctx.env, "Badge"),
+                art: art.clone(),
+            },
+        );
+        ctx.client.award_badge(&ctx.admin, &token_id, &badge_id);
+    }
+    ctx.client.token_uri(&(token_id as u32));
+
+    let mut template = [b'.'; types::MAX_TEMPLATE_LEN as usize];
+    let mut offset = 0;
+    for i in 0..types::MAX_TEMPLATE_SLOTS {
+        let slot = if i == 0 { "{badges}" } else { "{username}" };
+        template[offset..offset + slot.len()].copy_from_slice(slot.as_bytes());
+        offset += slot.len();
+    }
+    ctx.client.set_svg_template(
+        &ctx.admin,
+        &Tier::Architect,
+        &String::from_bytes(&ctx.env, &template),
+    );
+    ctx.client.token_uri(&(token_id as u32));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_svg_template_with_repeated_badges_slot_fails() {
+    let ctx = setup();
+    ctx.client.set_svg_template(
+        &ctx.admin,
+        &Tier::Pro,
+        &String::from_str(&ctx.env, "<svg>{badges}{badges}</svg>"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #29)")]
+fn test_award_undefined_badge_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    ctx.client.award_badge(&ctx.admin, &token_id, &1u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_award_badge_twice_fails() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "<circle r='16'/>");
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    ctx.client.award_badge(&ctx.admin, &token_id, &1u32);
+    ctx.client.award_badge(&ctx.admin, &token_id, &1u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_award_too_many_badges_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    for badge_id in 0..=types::MAX_BADGES_PER_TOKEN {
+        define_badge(&ctx, badge_id, "Badge", "<circle r='16'/>");
+        ctx.client.award_badge(&ctx.admin, &token_id, &badge_id);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_award_badge_missing_token_fails() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "<circle r='16'/>");
+    ctx.client.award_badge(&ctx.admin, &999u64, &1u32);
+}
+
+#[test]
+fn test_attester_awards_badge() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "<circle r='16'/>");
+    let attester = Address::generate(&ctx.env);
+    ctx.client.add_attester(&ctx.admin, &attester);
+    assert!(ctx.client.is_attester(&attester));
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client.award_badge(&attester, &token_id, &1u32);
+    assert_eq!(
+        ctx.client.list_badges(&token_id),
+        Vec::from_array(&ctx.env, [1u32])
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_self_awarded_badge_fails() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "<circle r='16'/>");
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client.award_badge(&user, &token_id, &1u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_award_badge_by_removed_attester_fails() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "<circle r='16'/>");
+    let attester = Address::generate(&ctx.env);
+    ctx.client.add_attester(&ctx.admin, &attester);
+    ctx.client.remove_attester(&ctx.admin, &attester);
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+
+    ctx.client.award_badge(&attester, &token_id, &1u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_add_attester_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.add_attester(&not_admin, &not_admin);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #28)")]
+fn test_define_badge_with_empty_art_fails() {
+    let ctx = setup();
+    define_badge(&ctx, 1, "First PR", "");
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_define_badge_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.define_badge(
+        &not_admin,
+        &1u32,
+        &BadgeDefinition {
+            name: String::from_str(&ctx.env, "First PR"),
+            art: String::from_str(&ctx.env, "<circle r='16'/>"),
+        },
+    );
+}
+
+fn org_membership(ctx: &TestEnv, org_id: u64, role: OrgRole, expires_at: u64) -> OrgMembership {
+    OrgMembership {
+        org_login: String::from_str(&ctx.env, "stellar"),
+        org_id,
+        role,
+        expires_at,
+    }
+}
+
+#[test]
+fn test_attest_org_membership_until_expiry() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let expires_at = ctx.env.ledger().timestamp() + 30 * DAY;
+    let membership = org_membership(&ctx, 42, OrgRole::Member, expires_at);
+
+    ctx.client.attest_org(&ctx.admin, &token_id, &membership);
+
+    assert!(ctx.client.is_member(&token_id, &42u64));
+    assert!(!ctx.client.is_member(&token_id, &43u64));
+    assert_eq!(
+        ctx.client.get_orgs(&token_id),
+        Vec::from_array(&ctx.env, [membership])
+    );
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = expires_at);
+    assert!(!ctx.client.is_member(&token_id, &42u64));
+    assert_eq!(ctx.client.get_orgs(&token_id), Vec::new(&ctx.env));
+}
+
+#[test]
+fn test_reattesting_org_replaces_claim() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let expires_at = ctx.env.ledger().timestamp() + 30 * DAY;
+
+    ctx.client.attest_org(
+        &ctx.admin,
+        &token_id,
+        &org_membership(&ctx, 42, OrgRole::Member, expires_at),
+    );
+    let promoted = org_membership(&ctx, 42, OrgRole::Admin, expires_at + DAY);
+    ctx.client.attest_org(&ctx.admin, &token_id, &promoted);
+
+    assert_eq!(
+        ctx.client.get_orgs(&token_id),
+        Vec::from_array(&ctx.env, [promoted])
+    );
+}
+
+#[test]
+fn test_expired_org_claims_free_room() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let expires_at = ctx.env.ledger().timestamp() + DAY;
+    for org_id in 0..types::MAX_ORGS_PER_TOKEN as u64 {
+        ctx.client.attest_org(
+            &ctx.admin,
+            &token_id,
+            &org_membership(&ctx, org_id, OrgRole::Member, expires_at),
+        );
+    }
+
+    ctx.env.ledger().with_mut(|li| li.timestamp = expires_at);
+    ctx.client.attest_org(
+        &ctx.admin,
+        &token_id,
+        &org_membership(&ctx, 100, OrgRole::Member, expires_at + DAY),
+    );
+    assert_eq!(ctx.client.get_orgs(&token_id).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn test_attest_too_many_orgs_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let expires_at = ctx.env.ledger().timestamp() + DAY;
+    for org_id in 0..=types::MAX_ORGS_PER_TOKEN as u64 {
+        ctx.client.attest_org(
+            &ctx.admin,
+            &token_id,
+            &org_membership(&ctx, org_id, OrgRole::Member, expires_at),
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_attest_expired_org_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let now = ctx.env.ledger().timestamp();
+    ctx.client.attest_org(
+        &ctx.admin,
+        &token_id,
+        &org_membership(&ctx, 42, OrgRole::Member, now),
+    );
+}
+
+#[test]
+fn test_attester_attests_org() {
+    let ctx = setup();
+    let attester = Address::generate(&ctx.env);
+    ctx.client.add_attester(&ctx.admin, &attester);
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let expires_at = ctx.env.ledger().timestamp() + DAY;
+
+    ctx.client.attest_org(
+        &attester,
+        &token_id,
+        &org_membership(&ctx, 42, OrgRole::Member, expires_at),
+    );
+    assert!(ctx.client.is_member(&token_id, &42u64));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_self_attested_org_claim_fails() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &user, "devfelipenunes", 1500);
+    let expires_at = ctx.env.ledger().timestamp() + DAY;
+
+    ctx.client.attest_org(
+        &user,
+        &token_id,
+        &org_membership(&ctx, 42, OrgRole::Admin, expires_at),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_attest_org_without_token_fails() {
+    let ctx = setup();
+    let expires_at = ctx.env.ledger().timestamp() + DAY;
+    ctx.client.attest_org(
+        &ctx.admin,
+        &999u64,
+        &org_membership(&ctx, 42, OrgRole::Member, expires_at),
+    );
+}
+
+#[test]
+fn test_is_member_without_token() {
+    let ctx = setup();
+    assert!(!ctx.client.is_member(&999u64, &42u64));
+}
+
+#[test]
+fn test_set_mint_fee_by_admin() {
+    let ctx = setup();
+    assert_eq!(ctx.client.get_mint_fee(), 0);
+    ctx.client.set_mint_fee(&ctx.admin, &5_000_000i128);
+    assert_eq!(ctx.client.get_mint_fee(), 5_000_000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_mint_fee_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.set_mint_fee(&not_admin, &5_000_000i128);
+}
+
+fn enable_mint_fee(ctx: &TestEnv, fee: i128) -> token::TokenClient<'_> {
+    let fee_token = ctx
+        .env
+        .register_stellar_asset_contract_v2(ctx.admin.clone())
+        .address();
+    ctx.client.set_fee_token(&ctx.admin, &fee_token);
+    ctx.client.set_mint_fee(&ctx.admin, &fee);
+    token::TokenClient::new(&ctx.env, &fee_token)
+}
+
+fn sponsored_mint(ctx: &TestEnv, sponsor: &Address, recipient: &Address, nonce: u64) -> u64 {
+    ctx.client.mint_for(
+        sponsor,
+        recipient,
+        &stub_signature(&ctx.env),
+        &String::from_str(&ctx.env, "newcomer"),
+        &1500u32,
+        &DeveloperMetrics::default(),
+        &Vec::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+        &nonce,
+    )
+}
+
+#[test]
+fn test_sponsored_mint_charges_sponsor_and_binds_recipient() {
+    let ctx = setup();
+    let fee_token = enable_mint_fee(&ctx, 1_000);
+    assert_eq!(ctx.client.get_fee_token(), Some(fee_token.address.clone()));
+    let sponsor = Address::generate(&ctx.env);
+    let recipient = Address::generate(&ctx.env);
+    token::StellarAssetClient::new(&ctx.env, &fee_token.address).mint(&sponsor, &5_000);
+
+    let token_id = sponsored_mint(&ctx, &sponsor, &recipient, 0);
+
+    let auths = ctx.env.auths();
+    assert!(auths.iter().any(|(address, _)| address == &sponsor));
+    assert!(auths.iter().any(|(address, _)| address == &recipient));
+
+    assert_eq!(ctx.client.owner_of(&(token_id as u32)), recipient);
+    assert!(!ctx.client.has_identity(&sponsor));
+    assert_eq!(ctx.client.get_nonce(&recipient), 1);
+    assert_eq!(fee_token.balance(&sponsor), 4_000);
+    assert_eq!(fee_token.balance(&ctx.treasury), 1_000);
+}
+
+#[test]
+fn test_mint_with_fee_charges_caller() {
+    let ctx = setup();
+    let fee_token = enable_mint_fee(&ctx, 1_000);
+    let user = Address::generate(&ctx.env);
+    token::StellarAssetClient::new(&ctx.env, &fee_token.address).mint(&user, &1_000);
+
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+    assert_eq!(fee_token.balance(&user), 0);
+    assert_eq!(fee_token.balance(&ctx.treasury), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_mint_with_fee_but_no_fee_token_fails() {
+    let ctx = setup();
+    ctx.client.set_mint_fee(&ctx.admin, &1_000i128);
+    let user = Address::generate(&ctx.env);
+    mint_for(&ctx, &user, "devfelipenunes", 1500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_sponsored_mint_for_existing_holder_fails() {
+    let ctx = setup();
+    let sponsor = Address::generate(&ctx.env);
+    let recipient = Address::generate(&ctx.env);
+    mint_for(&ctx, &recipient, "devfelipenunes", 1500);
+    sponsored_mint(&ctx, &sponsor, &recipient, 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_sponsored_mint_with_sponsor_nonce_fails() {
+    let ctx = setup();
+    let sponsor = Address::generate(&ctx.env);
+    let recipient = Address::generate(&ctx.env);
+    let first = Address::generate(&ctx.env);
+    sponsored_mint(&ctx, &sponsor, &first, 0);
+    sponsored_mint(&ctx, &sponsor, &recipient, 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_fee_token_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.set_fee_token(&not_admin, &not_admin);
+}
+
+fn batch_entry(ctx: &TestEnv, recipient: &Address, contributions: u32) -> BatchMintEntry {
+    BatchMintEntry {
+        recipient: recipient.clone(),
+        username: String::from_str(&ctx.env, "migrated"),
+        contributions,
+        metrics: DeveloperMetrics::default(),
+        languages: Vec::new(&ctx.env),
+        proof_data: Bytes::new(&ctx.env),
+    }
+}
+
+#[test]
+fn test_batch_mint_skips_existing_holders() {
+    let ctx = setup();
+    let existing = Address::generate(&ctx.env);
+    let existing_id = mint_for(&ctx, &existing, "devfelipenunes", 1500);
+    let alice = Address::generate(&ctx.env);
+    let entries = Vec::from_array(
+        &ctx.env,
+        [
+            batch_entry(&ctx, &alice, 50),
+            batch_entry(&ctx, &existing, 6000),
+            batch_entry(&ctx, &alice, 6000),
+        ],
+    );
+
+    let before = ctx.env.events().all().len();
+    let result = ctx.client.batch_mint(&ctx.admin, &entries);
+    assert_eq!(ctx.env.events().all().len(), before + 1);
+
+    assert_eq!(result.minted, Vec::from_array(&ctx.env, [existing_id + 1]));
+    assert_eq!(result.skipped, Vec::from_array(&ctx.env, [existing, alice]));
+    assert_eq!(
+        ctx.client.get_token_data(&(existing_id + 1)).tier,
+        Tier::Novice
+    );
+    assert_eq!(ctx.client.get_token_data(&existing_id).contributions, 1500);
+}
+
+#[test]
+fn test_full_batch_mint_by_minter_fits_write_limit_without_fee() {
+    let ctx = setup();
+    ctx.client.set_mint_fee(&ctx.admin, &1_000i128);
+    let minter = Address::generate(&ctx.env);
+    ctx.client.add_minter(&ctx.admin, &minter);
+    assert!(ctx.client.is_minter(&minter));
+
+    let mut entries = Vec::new(&ctx.env);
+    for _ in 0..types::MAX_MINT_BATCH {
+        entries.push_back(batch_entry(&ctx, &Address::generate(&ctx.env), 1500));
+    }
+
+    ctx.env.budget().reset_default();
+    let writes = written_entries(&ctx.env, || {
+        let result = ctx.client.batch_mint(&minter, &entries);
+        assert_eq!(result.minted.len(), types::MAX_MINT_BATCH);
+        assert_eq!(result.skipped.len(), 0);
+    });
+    // Six entries per identity, plus the token counter and the minter's auth nonce.
+    assert_eq!(writes, 6 * types::MAX_MINT_BATCH + 2);
+    assert!(writes <= types::TX_MAX_WRITE_ENTRIES);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_batch_mint_too_large_fails() {
+    let ctx = setup();
+    let mut entries = Vec::new(&ctx.env);
+    for _ in 0..=types::MAX_MINT_BATCH {
+        entries.push_back(batch_entry(&ctx, &Address::generate(&ctx.env), 1500));
+    }
+    ctx.client.batch_mint(&ctx.admin, &entries);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn test_batch_mint_by_removed_minter_fails() {
+    let ctx = setup();
+    let minter = Address::generate(&ctx.env);
+    ctx.client.add_minter(&ctx.admin, &minter);
+    ctx.client.remove_minter(&ctx.admin, &minter);
+
+    let recipient = Address::generate(&ctx.env);
+    let entries = Vec::from_array(&ctx.env, [batch_entry(&ctx, &recipient, 1500)]);
+    ctx.client.batch_mint(&minter, &entries);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_add_minter_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    ctx.client.add_minter(&not_admin, &not_admin);
+}
+
+#[test]
+fn test_set_access_control_by_admin() {
+    let ctx = setup();
+    let new_ac = Address::generate(&ctx.env);
+    ctx.client.set_access_control(&ctx.admin, &new_ac);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_access_control_by_non_admin_fails() {
+    let ctx = setup();
+    let not_admin = Address::generate(&ctx.env);
+    let new_ac = Address::generate(&ctx.env);
+    ctx.client.set_access_control(&not_admin, &new_ac);
 }
 
 #[test]
@@ -415,4 +2794,239 @@ fn test_set_treasury_by_non_admin_fails() {
     let not_admin = Address::generate(&ctx.env);
     let new_treasury = Address::generate(&ctx.env);
     ctx.client.set_treasury(&not_admin, &new_treasury);
-}
\ No newline at end of file
+}
+fn guardians_for(ctx: &TestEnv, holder: &Address, count: usize, threshold: u32) -> Vec<Address> {
+    let mut guardians = Vec::new(&ctx.env);
+    for _ in 0..count {
+        guardians.push_back(Address::generate(&ctx.env));
+    }
+    ctx.client.set_guardians(holder, &guardians, &threshold);
+    guardians
+}
+
+#[test]
+fn test_set_guardians_stores_set() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+
+    let guardians = guardians_for(&ctx, &holder, 3, 2);
+
+    let stored = ctx.client.get_guardians(&holder);
+    assert_eq!(stored.guardians, guardians);
+    assert_eq!(stored.threshold, 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_set_guardians_threshold_above_count_fails() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+
+    guardians_for(&ctx, &holder, 2, 3);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_set_guardians_including_holder_fails() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+
+    let guardians = Vec::from_array(&ctx.env, [holder.clone(), Address::generate(&ctx.env)]);
+    ctx.client.set_guardians(&holder, &guardians, &1);
+}
+
+#[test]
+fn test_recovery_moves_token_after_delay() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 3, 2);
+
+    ctx.client
+        .initiate_recovery(&guardians.get(0).unwrap(), &holder, &new_owner);
+    ctx.client
+        .approve_recovery(&guardians.get(1).unwrap(), &holder);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::RECOVERY_DELAY_SECONDS);
+    ctx.client.execute_recovery(&holder);
+
+    assert!(!ctx.client.has_identity(&holder));
+    assert!(ctx.client.has_identity(&new_owner));
+    assert_eq!(ctx.client.get_user_token(&new_owner), token_id);
+    assert_eq!(ctx.client.owner_of(&(token_id as u32)), new_owner);
+    assert_eq!(ctx.client.get_guardians(&new_owner).guardians, guardians);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_execute_recovery_before_delay_fails() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 2, 2);
+
+    ctx.client
+        .initiate_recovery(&guardians.get(0).unwrap(), &holder, &new_owner);
+    ctx.client
+        .approve_recovery(&guardians.get(1).unwrap(), &holder);
+    ctx.client.execute_recovery(&holder);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_execute_recovery_below_threshold_fails() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 3, 2);
+
+    ctx.client
+        .initiate_recovery(&guardians.get(0).unwrap(), &holder, &new_owner);
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::RECOVERY_DELAY_SECONDS);
+    ctx.client.execute_recovery(&holder);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_cancel_recovery_by_holder() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 1, 1);
+
+    ctx.client
+        .initiate_recovery(&guardians.get(0).unwrap(), &holder, &new_owner);
+    ctx.client.cancel_recovery(&holder);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::RECOVERY_DELAY_SECONDS);
+    ctx.client.execute_recovery(&holder);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_initiate_recovery_by_non_guardian_fails() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let stranger = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    guardians_for(&ctx, &holder, 2, 1);
+
+    ctx.client.initiate_recovery(&stranger, &holder, &stranger);
+}
+
+#[test]
+fn test_guardian_objections_cancel_without_replacing() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let attacker_wallet = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    let token_id = mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 4, 2);
+    let hostile = guardians.get(0).unwrap();
+    let accomplice = guardians.get(1).unwrap();
+    let first = guardians.get(2).unwrap();
+    let second = guardians.get(3).unwrap();
+
+    ctx.client
+        .initiate_recovery(&hostile, &holder, &attacker_wallet);
+    ctx.client.approve_recovery(&accomplice, &holder);
+
+    // A single objection is recorded but leaves the pending request in place.
+    ctx.client.object_recovery(&first, &holder);
+    let pending = ctx.client.get_recovery(&holder);
+    assert_eq!(pending.new_owner, attacker_wallet);
+    assert_eq!(
+        pending.objections,
+        Vec::from_array(&ctx.env, [first.clone()])
+    );
+
+    // The threshold-th objection drops the request without opening another.
+    ctx.client.object_recovery(&second, &holder);
+    assert!(ctx.client.try_get_recovery(&holder).is_err());
+
+    ctx.client.initiate_recovery(&first, &holder, &new_owner);
+    ctx.client.approve_recovery(&second, &holder);
+    let opened_at = ctx.client.get_recovery(&holder).initiated_at;
+
+    // The other group can object too, but below threshold it neither drops nor
+    // restarts the request.
+    ctx.client.object_recovery(&hostile, &holder);
+    let pending = ctx.client.get_recovery(&holder);
+    assert_eq!(pending.new_owner, new_owner);
+    assert_eq!(pending.initiated_at, opened_at);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::RECOVERY_DELAY_SECONDS);
+    ctx.client.execute_recovery(&holder);
+
+    assert_eq!(ctx.client.owner_of(&(token_id as u32)), new_owner);
+    assert!(!ctx.client.has_identity(&attacker_wallet));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_initiate_recovery_while_pending_fails() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 3, 2);
+
+    ctx.client
+        .initiate_recovery(&guardians.get(0).unwrap(), &holder, &new_owner);
+    ctx.client
+        .initiate_recovery(&guardians.get(1).unwrap(), &holder, &new_owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_objection_withdraws_approval() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 3, 2);
+
+    ctx.client
+        .initiate_recovery(&guardians.get(0).unwrap(), &holder, &new_owner);
+    ctx.client
+        .approve_recovery(&guardians.get(1).unwrap(), &holder);
+    ctx.client
+        .object_recovery(&guardians.get(1).unwrap(), &holder);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.timestamp += types::RECOVERY_DELAY_SECONDS);
+    ctx.client.execute_recovery(&holder);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn test_duplicate_recovery_objection_fails() {
+    let ctx = setup();
+    let holder = Address::generate(&ctx.env);
+    let new_owner = Address::generate(&ctx.env);
+    mint_for(&ctx, &holder, "devfelipenunes", 1500);
+    let guardians = guardians_for(&ctx, &holder, 3, 3);
+
+    ctx.client
+        .initiate_recovery(&guardians.get(0).unwrap(), &holder, &new_owner);
+    ctx.client
+        .object_recovery(&guardians.get(1).unwrap(), &holder);
+    ctx.client
+        .object_recovery(&guardians.get(1).unwrap(), &holder);
+}
@@ -1,4 +1,75 @@
-use soroban_sdk::{contracttype, contracterror, Bytes, Env, String};
+use soroban_sdk::{contracttype, contracterror, Address, Bytes, Env, String, Vec};
+
+pub const TOKEN_NAME: &str = "GitHub Identity";
+pub const TOKEN_SYMBOL: &str = "GHID";
+pub const TOKEN_DESCRIPTION: &str =
+    "Soulbound identity token attesting GitHub developer activity on Stellar.";
+
+pub const MAX_USERNAME_LEN: u32 = 39;
+
+/// Default minimum developer score (`ScoreWeights::score`) for Pro, Architect, Legend and
+/// Singularity, in order.
+pub const DEFAULT_TIER_THRESHOLDS: [u32; 4] = [200, 1000, 3000, 5000];
+/// One threshold per tier above Novice. Tables longer than the default unlock Mythic and
+/// Transcendent.
+pub const MAX_TIER_THRESHOLDS: u32 = 6;
+
+/// Score weights are expressed in basis points: 10_000 counts a metric one-for-one.
+pub const WEIGHT_SCALE: u64 = 10_000;
+pub const MAX_SCORE_WEIGHT: u32 = 1_000_000;
+
+pub const ACTIVITY_HALF_LIFE_SECONDS: u64 = 180 * 24 * 60 * 60;
+pub const ACTIVITY_WINDOW_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+pub const MAX_REPUTATION: u32 = 1000;
+pub const REPUTATION_AGE_SATURATION_SECONDS: u64 = 365 * 24 * 60 * 60;
+pub const REPAYMENT_SATURATION: u32 = 10;
+pub const VOUCH_SATURATION: u32 = 500;
+
+pub const MIN_VOUCHER_TIER: Tier = Tier::Pro;
+pub const MAX_VOUCH_WEIGHT: u32 = 100;
+pub const MAX_VOUCHES_PER_VOUCHER: u32 = 5;
+/// Bounds the vouch list `reputation_score` walks; well above what `VOUCH_SATURATION` needs.
+pub const MAX_VOUCHES_RECEIVED: u32 = 20;
+
+/// Per-transaction ledger entry limits of the Soroban network. Batch entry points
+/// are sized so a full batch fits; every call also spends one write on the caller's
+/// auth nonce.
+pub const TX_MAX_READ_ENTRIES: u32 = 40;
+pub const TX_MAX_WRITE_ENTRIES: u32 = 25;
+
+/// `clear_cluster` removes every token's link and the cluster itself in one call.
+pub const MAX_CLUSTER_SIZE: u32 = TX_MAX_WRITE_ENTRIES - 2;
+/// Each linked token reads its data and link; the rest of the read budget covers the
+/// caller's role, the cluster, its counter and the contract itself.
+pub const MAX_CLUSTER_LINK_BATCH: u32 = (TX_MAX_READ_ENTRIES - 8) / 2;
+pub const MAX_CLUSTER_REASON_LEN: u32 = 256;
+
+pub const MAX_HISTORY_ENTRIES: u32 = 64;
+pub const MAX_HISTORY_PAGE: u32 = 20;
+
+/// A re-tier rewrites the token's data and history.
+pub const MAX_RECOMPUTE_BATCH: u32 = (TX_MAX_WRITE_ENTRIES - 1) / 2;
+/// A mint writes the token's data, history, owner, holder index, identity flag and
+/// nonce; the batch shares the token counter.
+pub const MAX_MINT_BATCH: u32 = (TX_MAX_WRITE_ENTRIES - 2) / 6;
+
+pub const MAX_TEMPLATE_LEN: u32 = 1024;
+pub const MAX_TEMPLATE_SLOTS: u32 = 8;
+
+pub const MAX_BADGE_NAME_LEN: u32 = 32;
+pub const MAX_BADGE_ART_LEN: u32 = 128;
+pub const MAX_BADGES_PER_TOKEN: u32 = 8;
+
+pub const MAX_LANGUAGES: u32 = 10;
+pub const MAX_LANGUAGE_NAME_LEN: u32 = 32;
+pub const BPS_SCALE: u32 = 10_000;
+
+pub const MAX_ORG_LOGIN_LEN: u32 = 39;
+pub const MAX_ORGS_PER_TOKEN: u32 = 10;
+
+pub const MAX_GUARDIANS: u32 = 10;
+pub const RECOVERY_DELAY_SECONDS: u64 = 7 * 24 * 60 * 60;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -18,6 +89,40 @@ pub enum Error {
     AccessControlError = 12,
     Unauthorized = 13,
     AlreadyInitialized = 14,
+    InvalidGuardians = 15,
+    NotGuardian = 16,
+    RecoveryPending = 17,
+    NoRecoveryPending = 18,
+    RecoveryNotReady = 19,
+    AlreadyApproved = 20,
+    UsernameTooLong = 21,
+    InvalidTemplate = 22,
+    InvalidThresholds = 23,
+    BatchTooLarge = 24,
+    InvalidWeights = 25,
+    AccountTooNew = 26,
+    TooSoon = 27,
+    InvalidBadge = 28,
+    BadgeNotFound = 29,
+    BadgeAlreadyAwarded = 30,
+    TooManyBadges = 31,
+    InvalidMembership = 32,
+    TooManyOrgs = 33,
+    InvalidLanguages = 34,
+    TierTooLow = 35,
+    NotReporter = 36,
+    InvalidAmount = 37,
+    InvalidVouch = 38,
+    TooManyVouches = 39,
+    AlreadyVouched = 40,
+    VouchNotFound = 41,
+    InvalidCluster = 42,
+    AlreadyFlagged = 43,
+    ClusterNotFound = 44,
+    IdentityFlagged = 45,
+    NotMinter = 46,
+    AlreadyObjected = 47,
+    NotAttester = 48,
 }
 
 #[contracttype]
@@ -26,11 +131,329 @@ pub struct GithubData {
     pub username: String,
     pub contributions: u32,
     pub tier: Tier,
+    pub metrics: DeveloperMetrics,
+    pub languages: Vec<LanguageShare>,
     pub minted_at: u64,
     pub updated_at: u64,
     pub proof_data: Bytes,
 }
 
+/// GitHub data for one identity. `batch_mint` takes these pre-verified, and `mint`
+/// and `mint_for` build one from their arguments.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchMintEntry {
+    pub recipient: Address,
+    pub username: String,
+    pub contributions: u32,
+    pub metrics: DeveloperMetrics,
+    pub languages: Vec<LanguageShare>,
+    pub proof_data: Bytes,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchMintResult {
+    pub minted: Vec<u64>,
+    /// Recipients that already held an identity, in entry order.
+    pub skipped: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IdentityStatus {
+    Active,
+    Stale,
+}
+
+impl IdentityStatus {
+    /// A zero validity period means identities never go stale.
+    pub fn at(updated_at: u64, validity_period: u64, now: u64) -> Self {
+        if validity_period > 0 && now >= updated_at.saturating_add(validity_period) {
+            IdentityStatus::Stale
+        } else {
+            IdentityStatus::Active
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryEntry {
+    pub timestamp: u64,
+    pub contributions: u32,
+    pub tier: Tier,
+}
+
+/// One entry of a language breakdown; `share_bps` is the share of the developer's
+/// code in basis points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LanguageShare {
+    pub name: String,
+    pub share_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeveloperMetrics {
+    pub merged_prs: u32,
+    pub reviews: u32,
+    pub repos_owned: u32,
+    pub stars_received: u32,
+    pub followers: u32,
+    pub account_created_at: u64,
+    pub longest_streak: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreWeights {
+    pub contributions: u32,
+    pub merged_prs: u32,
+    pub reviews: u32,
+    pub repos_owned: u32,
+    pub stars_received: u32,
+    pub followers: u32,
+    pub longest_streak: u32,
+}
+
+impl DeveloperMetrics {
+    /// An unknown creation date (`0`) never satisfies a non-zero minimum age.
+    pub fn check_account_age(&self, min_age: u64, now: u64) -> Result<(), Error> {
+        if min_age == 0 {
+            return Ok(());
+        }
+        if self.account_created_at == 0 || now.saturating_sub(self.account_created_at) < min_age {
+            return Err(Error::AccountTooNew);
+        }
+        Ok(())
+    }
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights {
+            contributions: WEIGHT_SCALE as u32,
+            merged_prs: 0,
+            reviews: 0,
+            repos_owned: 0,
+            stars_received: 0,
+            followers: 0,
+            longest_streak: 0,
+        }
+    }
+}
+
+impl ScoreWeights {
+    fn weights(&self) -> [u32; 7] {
+        [
+            self.contributions,
+            self.merged_prs,
+            self.reviews,
+            self.repos_owned,
+            self.stars_received,
+            self.followers,
+            self.longest_streak,
+        ]
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let weights = self.weights();
+        if weights.iter().all(|weight| *weight == 0) {
+            return Err(Error::InvalidWeights);
+        }
+        if weights.iter().any(|weight| *weight > MAX_SCORE_WEIGHT) {
+            return Err(Error::InvalidWeights);
+        }
+        Ok(())
+    }
+
+    pub fn score(&self, contributions: u32, metrics: &DeveloperMetrics) -> u32 {
+        let values = [
+            contributions,
+            metrics.merged_prs,
+            metrics.reviews,
+            metrics.repos_owned,
+            metrics.stars_received,
+            metrics.followers,
+            metrics.longest_streak,
+        ];
+        let weighted: u64 = values
+            .iter()
+            .zip(self.weights().iter())
+            .map(|(value, weight)| *value as u64 * *weight as u64)
+            .sum();
+        (weighted / WEIGHT_SCALE).min(u32::MAX as u64) as u32
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CreditEventKind {
+    Repaid,
+    Late,
+    Defaulted,
+}
+
+/// Running totals of the credit outcomes reporters have recorded for an identity.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CreditSummary {
+    pub repaid_count: u32,
+    pub repaid_amount: i128,
+    pub late_count: u32,
+    pub late_amount: i128,
+    pub defaulted_count: u32,
+    pub defaulted_amount: i128,
+    pub last_reported_at: u64,
+}
+
+impl CreditSummary {
+    pub fn record(&mut self, kind: &CreditEventKind, amount: i128, now: u64) {
+        let (count, total) = match kind {
+            CreditEventKind::Repaid => (&mut self.repaid_count, &mut self.repaid_amount),
+            CreditEventKind::Late => (&mut self.late_count, &mut self.late_amount),
+            CreditEventKind::Defaulted => (&mut self.defaulted_count, &mut self.defaulted_amount),
+        };
+        *count = count.saturating_add(1);
+        *total = total.saturating_add(amount);
+        self.last_reported_at = now;
+    }
+}
+
+/// A vouch received by an identity. `voucher` is the vouching token, so the vouch
+/// follows it through recovery; `stake_token` records where `stake` is held.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Vouch {
+    pub voucher: u64,
+    pub weight: u32,
+    pub stake: i128,
+    pub stake_token: Option<Address>,
+    pub created_at: u64,
+}
+
+/// Identities believed to be controlled by a single actor.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cluster {
+    pub id: u64,
+    pub token_ids: Vec<u64>,
+    pub reason: String,
+    pub flagged_by: Address,
+    pub flagged_at: u64,
+}
+
+pub fn validate_cluster_reason(reason: &String) -> Result<(), Error> {
+    if reason.is_empty() || reason.len() > MAX_CLUSTER_REASON_LEN {
+        return Err(Error::InvalidCluster);
+    }
+    Ok(())
+}
+
+/// Points each factor contributes to the reputation score at its best value; they
+/// must sum to `MAX_REPUTATION`. The penalties are subtracted per reported late
+/// payment or default, or once for membership of a flagged cluster, and may each
+/// be at most `MAX_REPUTATION`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationWeights {
+    pub tier: u32,
+    pub developer_score: u32,
+    pub identity_age: u32,
+    pub freshness: u32,
+    pub repayments: u32,
+    pub vouches: u32,
+    pub late_penalty: u32,
+    pub default_penalty: u32,
+    pub cluster_penalty: u32,
+}
+
+impl Default for ReputationWeights {
+    fn default() -> Self {
+        ReputationWeights {
+            tier: 250,
+            developer_score: 250,
+            identity_age: 150,
+            freshness: 150,
+            repayments: 100,
+            vouches: 100,
+            late_penalty: 50,
+            default_penalty: 250,
+            cluster_penalty: 500,
+        }
+    }
+}
+
+/// Inputs to the reputation score, gathered from an identity's stored data.
+pub struct ReputationFactors {
+    pub tier: Tier,
+    pub developer_score: u32,
+    /// Developer score that earns the full `developer_score` weight.
+    pub score_ceiling: u32,
+    /// Number of thresholds in the active table; its top tier earns the full `tier` weight.
+    pub tier_ceiling: u32,
+    pub identity_age: u64,
+    pub fresh: bool,
+    pub credit: CreditSummary,
+    /// Total weight of the vouches the identity has received.
+    pub vouch_weight: u32,
+    pub flagged: bool,
+}
+
+impl ReputationWeights {
+    pub fn validate(&self) -> Result<(), Error> {
+        let total = self.tier as u64
+            + self.developer_score as u64
+            + self.identity_age as u64
+            + self.freshness as u64
+            + self.repayments as u64
+            + self.vouches as u64;
+        if total != MAX_REPUTATION as u64 {
+            return Err(Error::InvalidWeights);
+        }
+        if self.late_penalty > MAX_REPUTATION
+            || self.default_penalty > MAX_REPUTATION
+            || self.cluster_penalty > MAX_REPUTATION
+        {
+            return Err(Error::InvalidWeights);
+        }
+        Ok(())
+    }
+
+    /// Each factor earns its weight in proportion to how far it is towards its best
+    /// value: the top tier of the active table, the score ceiling, `REPUTATION_AGE_SATURATION_SECONDS`
+    /// of identity age, a non-stale identity, `REPAYMENT_SATURATION` repayments and
+    /// `VOUCH_SATURATION` of received vouch weight.
+    /// Late payments and defaults then subtract their penalties.
+    pub fn score(&self, factors: &ReputationFactors) -> u32 {
+        let tier_ceiling = factors.tier_ceiling.max(1) as u64;
+        let tier_rank = ((factors.tier.to_number() - 1) as u64).min(tier_ceiling);
+        let ceiling = factors.score_ceiling.max(1) as u64;
+        let developer_score = (factors.developer_score as u64).min(ceiling);
+        let identity_age = factors.identity_age.min(REPUTATION_AGE_SATURATION_SECONDS);
+        let repayments = factors.credit.repaid_count.min(REPAYMENT_SATURATION) as u64;
+        let vouch_weight = factors.vouch_weight.min(VOUCH_SATURATION) as u64;
+
+        let mut points = self.tier as u64 * tier_rank / tier_ceiling
+            + self.developer_score as u64 * developer_score / ceiling
+            + self.identity_age as u64 * identity_age / REPUTATION_AGE_SATURATION_SECONDS
+            + self.repayments as u64 * repayments / REPAYMENT_SATURATION as u64
+            + self.vouches as u64 * vouch_weight / VOUCH_SATURATION as u64;
+        if factors.fresh {
+            points += self.freshness as u64;
+        }
+
+        let mut penalty = self.late_penalty as u64 * factors.credit.late_count as u64
+            + self.default_penalty as u64 * factors.credit.defaulted_count as u64;
+        if factors.flagged {
+            penalty += self.cluster_penalty as u64;
+        }
+        points.min(MAX_REPUTATION as u64).saturating_sub(penalty) as u32
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Tier {
@@ -39,16 +462,28 @@ pub enum Tier {
     Architect,
     Legend,
     Singularity,
+    Mythic,
+    Transcendent,
 }
 
 impl Tier {
-    pub fn from_contributions(contributions: u32) -> Self {
-        match contributions {
-            5000.. => Tier::Singularity,
-            3000..=4999 => Tier::Legend,
-            1000..=2999 => Tier::Architect,
-            200..=999 => Tier::Pro,
-            _ => Tier::Novice,
+    pub fn from_thresholds(score: u32, thresholds: &Vec<u32>) -> Self {
+        let rank = thresholds
+            .iter()
+            .filter(|threshold| score >= *threshold)
+            .count();
+        Self::from_rank(rank)
+    }
+
+    fn from_rank(rank: usize) -> Self {
+        match rank {
+            0 => Tier::Novice,
+            1 => Tier::Pro,
+            2 => Tier::Architect,
+            3 => Tier::Legend,
+            4 => Tier::Singularity,
+            5 => Tier::Mythic,
+            _ => Tier::Transcendent,
         }
     }
 
@@ -59,27 +494,186 @@ impl Tier {
             Tier::Architect => 3,
             Tier::Legend => 4,
             Tier::Singularity => 5,
+            Tier::Mythic => 6,
+            Tier::Transcendent => 7,
         }
     }
 
-    pub fn to_string(&self, env: &Env) -> String {
+    pub fn at_least(&self, min_tier: &Tier) -> bool {
+        self.to_number() >= min_tier.to_number()
+    }
+
+    pub fn theme(&self) -> TierTheme {
         match self {
-            Tier::Novice => String::from_str(env, "Novice"),
-            Tier::Pro => String::from_str(env, "Pro"),
-            Tier::Architect => String::from_str(env, "Architect"),
-            Tier::Legend => String::from_str(env, "Legend"),
-            Tier::Singularity => String::from_str(env, "Singularity"),
+            Tier::Novice => TierTheme {
+                background: "#2b1d0e",
+                foreground: "#f5e6d3",
+                accent: "#CD7F32",
+                label: "Novice",
+            },
+            Tier::Pro => TierTheme {
+                background: "#1f2326",
+                foreground: "#f2f2f2",
+                accent: "#C0C0C0",
+                label: "Pro",
+            },
+            Tier::Architect => TierTheme {
+                background: "#2a2200",
+                foreground: "#fff8dc",
+                accent: "#FFD700",
+                label: "Architect",
+            },
+            Tier::Legend => TierTheme {
+                background: "#202024",
+                foreground: "#ffffff",
+                accent: "#E5E4E2",
+                label: "Legend",
+            },
+            Tier::Singularity => TierTheme {
+                background: "#0b1a06",
+                foreground: "#eaffea",
+                accent: "#39FF14",
+                label: "Singularity",
+            },
+            Tier::Mythic => TierTheme {
+                background: "#1a0b2e",
+                foreground: "#f3e8ff",
+                accent: "#9B30FF",
+                label: "Mythic",
+            },
+            Tier::Transcendent => TierTheme {
+                background: "#001a1f",
+                foreground: "#e0ffff",
+                accent: "#00E5FF",
+                label: "Transcendent",
+            },
         }
     }
 
+    pub fn label(&self) -> &'static str {
+        self.theme().label
+    }
+
+    pub fn to_string(&self, env: &Env) -> String {
+        String::from_str(env, self.label())
+    }
+
     pub fn to_color(&self, env: &Env) -> String {
-        match self {
-            Tier::Novice => String::from_str(env, "#CD7F32"),
-            Tier::Pro => String::from_str(env, "#C0C0C0"),
-            Tier::Architect => String::from_str(env, "#FFD700"),
-            Tier::Legend => String::from_str(env, "#E5E4E2"),
-            Tier::Singularity => String::from_str(env, "#39FF14"),
+        String::from_str(env, self.theme().accent)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TierTheme {
+    pub background: &'static str,
+    pub foreground: &'static str,
+    pub accent: &'static str,
+    pub label: &'static str,
+}
+
+/// An admin-defined achievement. `art` is an SVG fragment drawn inside a 32x32 cell
+/// on the token image.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeDefinition {
+    pub name: String,
+    pub art: String,
+}
+
+impl BadgeDefinition {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.name.is_empty() || self.name.len() > MAX_BADGE_NAME_LEN {
+            return Err(Error::InvalidBadge);
+        }
+        if self.art.is_empty() || self.art.len() > MAX_BADGE_ART_LEN {
+            return Err(Error::InvalidBadge);
+        }
+        Ok(())
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OrgRole {
+    Member,
+    Admin,
+}
+
+/// An attested GitHub organization membership. `org_id` is GitHub's numeric id,
+/// which survives org renames; `org_login` is kept for display.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgMembership {
+    pub org_login: String,
+    pub org_id: u64,
+    pub role: OrgRole,
+    pub expires_at: u64,
+}
+
+impl OrgMembership {
+    pub fn validate(&self, now: u64) -> Result<(), Error> {
+        if self.org_login.is_empty() || self.org_login.len() > MAX_ORG_LOGIN_LEN {
+            return Err(Error::InvalidMembership);
+        }
+        if self.is_expired(now) {
+            return Err(Error::InvalidMembership);
+        }
+        Ok(())
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianSet {
+    pub guardians: Vec<Address>,
+    pub threshold: u32,
+}
+
+impl GuardianSet {
+    pub fn validate(&self, holder: &Address) -> Result<(), Error> {
+        let count = self.guardians.len();
+        if count == 0 || count > MAX_GUARDIANS {
+            return Err(Error::InvalidGuardians);
+        }
+        if self.threshold == 0 || self.threshold > count {
+            return Err(Error::InvalidGuardians);
         }
+        for (i, guardian) in self.guardians.iter().enumerate() {
+            if &guardian == holder {
+                return Err(Error::InvalidGuardians);
+            }
+            if self
+                .guardians
+                .iter()
+                .skip(i + 1)
+                .any(|other| other == guardian)
+            {
+                return Err(Error::InvalidGuardians);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub new_owner: Address,
+    pub approvals: Vec<Address>,
+    /// Guardians objecting to this request. Reaching the threshold drops it, so
+    /// one guardian cannot hold recovery hostage.
+    pub objections: Vec<Address>,
+    pub initiated_at: u64,
+}
+
+impl RecoveryRequest {
+    pub fn is_ready(&self, threshold: u32, now: u64) -> bool {
+        self.approvals.len() >= threshold
+            && now >= self.initiated_at.saturating_add(RECOVERY_DELAY_SECONDS)
     }
 }
 
@@ -92,13 +686,387 @@ pub struct Config {
     pub mint_fee: i128,
 }
 
-pub fn generate_svg(env: &Env, data: &GithubData) -> String {
-    let svg = match data.tier {
-        Tier::Novice => "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#b0c4de'/><text x='50%' y='100' font-size='24' fill='#181c2f' text-anchor='middle'>Novice</text></svg>",
-        Tier::Pro => "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#90ee90'/><text x='50%' y='100' font-size='24' fill='#181c2f' text-anchor='middle'>Pro</text></svg>",
-        Tier::Architect => "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#ffd700'/><text x='50%' y='100' font-size='24' fill='#181c2f' text-anchor='middle'>Architect</text></svg>",
-        Tier::Legend => "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#ff8c00'/><text x='50%' y='100' font-size='24' fill='#fff' text-anchor='middle'>Legend</text></svg>",
-        Tier::Singularity => "<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'><rect width='100%' height='100%' fill='#8a2be2'/><text x='50%' y='100' font-size='24' fill='#fff' text-anchor='middle'>Singularity</text></svg>",
-    };
-    String::from_str(env, svg)
-}
\ No newline at end of file
+/// Contribution growth since the start of the activity window. The baseline is the
+/// latest snapshot taken at or before the window start, or the oldest retained
+/// snapshot when the whole history falls inside the window.
+pub fn recent_contribution_delta(history: &Vec<HistoryEntry>, contributions: u32, now: u64) -> u32 {
+    let window_start = now.saturating_sub(ACTIVITY_WINDOW_SECONDS);
+    let baseline = history
+        .iter()
+        .filter(|entry| entry.timestamp <= window_start)
+        .last()
+        .or_else(|| history.first())
+        .map(|entry| entry.contributions)
+        .unwrap_or(contributions);
+    contributions.saturating_sub(baseline)
+}
+
+/// Score plus recent growth, halved for every `ACTIVITY_HALF_LIFE_SECONDS` since the
+/// last attested update and interpolated linearly within a half-life.
+pub fn activity_score(score: u32, recent_delta: u32, age: u64) -> u32 {
+    let halvings = age / ACTIVITY_HALF_LIFE_SECONDS;
+    if halvings >= 32 {
+        return 0;
+    }
+
+    let value = score.saturating_add(recent_delta) as u64 >> halvings;
+    let remainder = age % ACTIVITY_HALF_LIFE_SECONDS;
+    let decay = (value / 2) * remainder / ACTIVITY_HALF_LIFE_SECONDS;
+    (value - decay) as u32
+}
+
+/// One minimum per tier above Novice, in order. Tiers up to Singularity always exist, so
+/// the table may add tiers beyond the default but never drop one.
+pub fn validate_thresholds(thresholds: &Vec<u32>) -> Result<(), Error> {
+    if (thresholds.len() as usize) < DEFAULT_TIER_THRESHOLDS.len()
+        || thresholds.len() > MAX_TIER_THRESHOLDS
+    {
+        return Err(Error::InvalidThresholds);
+    }
+
+    let mut previous = 0u32;
+    for threshold in thresholds.iter() {
+        if threshold <= previous {
+            return Err(Error::InvalidThresholds);
+        }
+        previous = threshold;
+    }
+    Ok(())
+}
+
+pub fn validate_username(username: &String) -> Result<(), Error> {
+    if username.is_empty() {
+        return Err(Error::EmptyUsername);
+    }
+    if username.len() > MAX_USERNAME_LEN {
+        return Err(Error::UsernameTooLong);
+    }
+    Ok(())
+}
+
+/// Languages must be the top entries of the breakdown: at most `MAX_LANGUAGES`,
+/// ordered by descending share, with distinct non-empty names and shares summing
+/// to at most `BPS_SCALE`.
+pub fn validate_languages(languages: &Vec<LanguageShare>) -> Result<(), Error> {
+    if languages.len() > MAX_LANGUAGES {
+        return Err(Error::InvalidLanguages);
+    }
+
+    let mut total = 0u32;
+    let mut previous_share = u32::MAX;
+    for (i, language) in languages.iter().enumerate() {
+        if language.name.is_empty() || language.name.len() > MAX_LANGUAGE_NAME_LEN {
+            return Err(Error::InvalidLanguages);
+        }
+        if language.share_bps == 0 || language.share_bps > previous_share {
+            return Err(Error::InvalidLanguages);
+        }
+        if languages
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.name == language.name)
+        {
+            return Err(Error::InvalidLanguages);
+        }
+        total += language.share_bps;
+        previous_share = language.share_bps;
+    }
+
+    if total > BPS_SCALE {
+        return Err(Error::InvalidLanguages);
+    }
+    Ok(())
+}
+
+pub fn generate_svg(env: &Env, token_id: u64, data: &GithubData, badges: &Vec<String>) -> String {
+    let theme = data.tier.theme();
+    let username = TextBuffer::<USERNAME_CAPACITY>::from_string(&data.username);
+
+    let mut svg = TextBuffer::<SVG_CAPACITY>::new();
+    svg.push_str("<svg xmlns='http://www.w3.org/2000/svg' width='350' height='200'>");
+    svg.push_str("<rect width='100%' height='100%' fill='");
+    svg.push_str(theme.background);
+    svg.push_str("' stroke='");
+    svg.push_str(theme.accent);
+    svg.push_str("' stroke-width='4'/><text x='50%' y='60' font-size='24' fill='");
+    svg.push_str(theme.accent);
+    svg.push_str("' text-anchor='middle'>");
+    svg.push_str(theme.label);
+    svg.push_str("</text><text x='50%' y='100' font-size='18' fill='");
+    svg.push_str(theme.foreground);
+    svg.push_str("' text-anchor='middle'>@");
+    svg.push_xml_escaped(username.as_bytes());
+    svg.push_str("</text><text x='50%' y='135' font-size='14' fill='");
+    svg.push_str(theme.foreground);
+    svg.push_str("' text-anchor='middle'>");
+    svg.push_u64(data.contributions as u64);
+    svg.push_str(" contributions</text><text x='50%' y='175' font-size='11' fill='");
+    svg.push_str(theme.foreground);
+    svg.push_str("' text-anchor='middle'>#");
+    svg.push_u64(token_id);
+    svg.push_str(" - minted ");
+    svg.push_date(data.minted_at);
+    svg.push_str("</text>");
+    push_badges(&mut svg, badges);
+    svg.push_str("</svg>");
+    svg.to_string(env)
+}
+
+// Lays badges out left to right along the top edge, one 40px cell each.
+fn push_badges<const N: usize>(svg: &mut TextBuffer<N>, badges: &Vec<String>) {
+    for (i, art) in badges.iter().enumerate() {
+        let art = TextBuffer::<BADGE_ART_CAPACITY>::from_string(&art);
+        svg.push_str("<g transform='translate(");
+        svg.push_u64(8 + 40 * i as u64);
+        svg.push_str(",8)'>");
+        svg.push_bytes(art.as_bytes());
+        svg.push_str("</g>");
+    }
+}
+
+const USERNAME_CAPACITY: usize = MAX_USERNAME_LEN as usize;
+const BADGE_ART_CAPACITY: usize = MAX_BADGE_ART_LEN as usize;
+const TEMPLATE_CAPACITY: usize = MAX_TEMPLATE_LEN as usize;
+const SVG_CAPACITY: usize = 4096;
+const JSON_CAPACITY: usize = 6144;
+const URI_CAPACITY: usize = 8448;
+
+const TEMPLATE_SLOTS: [&str; 5] = [
+    "{username}",
+    "{contributions}",
+    "{tier}",
+    "{token_id}",
+    "{badges}",
+];
+const BADGES_SLOT: usize = 4;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub(crate) struct TextBuffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> TextBuffer<N> {
+    pub fn new() -> Self {
+        TextBuffer {
+            bytes: [0u8; N],
+            len: 0,
+        }
+    }
+
+    pub fn from_string(value: &String) -> Self {
+        let mut buffer = Self::new();
+        let len = value.len() as usize;
+        value.copy_into_slice(&mut buffer.bytes[..len]);
+        buffer.len = len;
+        buffer
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    pub fn push_byte(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+
+    pub fn push_str(&mut self, value: &str) {
+        self.push_bytes(value.as_bytes());
+    }
+
+    pub fn push_bytes(&mut self, value: &[u8]) {
+        let end = self.len + value.len();
+        self.bytes[self.len..end].copy_from_slice(value);
+        self.len = end;
+    }
+
+    pub fn push_u64(&mut self, value: u64) {
+        let mut digits = itoa::Buffer::new();
+        self.push_str(digits.format(value));
+    }
+
+    pub fn push_padded_u64(&mut self, value: u64, width: usize) {
+        let mut digits = itoa::Buffer::new();
+        let digits = digits.format(value);
+        for _ in digits.len()..width {
+            self.push_byte(b'0');
+        }
+        self.push_str(digits);
+    }
+
+    pub fn push_date(&mut self, timestamp: u64) {
+        let (year, month, day) = civil_from_days((timestamp / 86_400) as i64);
+        self.push_padded_u64(year as u64, 4);
+        self.push_byte(b'-');
+        self.push_padded_u64(month as u64, 2);
+        self.push_byte(b'-');
+        self.push_padded_u64(day as u64, 2);
+    }
+
+    pub fn push_xml_escaped(&mut self, input: &[u8]) {
+        for &byte in input {
+            match byte {
+                b'&' => self.push_str("&amp;"),
+                b'<' => self.push_str("&lt;"),
+                b'>' => self.push_str("&gt;"),
+                b'\'' => self.push_str("&apos;"),
+                b'"' => self.push_str("&quot;"),
+                _ => self.push_byte(byte),
+            }
+        }
+    }
+
+    pub fn push_base64(&mut self, input: &[u8]) {
+        for chunk in input.chunks(3) {
+            let b0 = chunk[0] as usize;
+            let b1 = chunk.get(1).copied().unwrap_or(0) as usize;
+            let b2 = chunk.get(2).copied().unwrap_or(0) as usize;
+            let triple = (b0 << 16) | (b1 << 8) | b2;
+
+            self.push_byte(BASE64_ALPHABET[(triple >> 18) & 63]);
+            self.push_byte(BASE64_ALPHABET[(triple >> 12) & 63]);
+            if chunk.len() > 1 {
+                self.push_byte(BASE64_ALPHABET[(triple >> 6) & 63]);
+            } else {
+                self.push_byte(b'=');
+            }
+            if chunk.len() > 2 {
+                self.push_byte(BASE64_ALPHABET[triple & 63]);
+            } else {
+                self.push_byte(b'=');
+            }
+        }
+    }
+
+    pub fn to_string(&self, env: &Env) -> String {
+        String::from_bytes(env, self.as_bytes())
+    }
+}
+
+fn match_template_slot(rest: &[u8]) -> Option<usize> {
+    TEMPLATE_SLOTS
+        .iter()
+        .position(|slot| rest.starts_with(slot.as_bytes()))
+}
+
+pub fn validate_template(template: &String) -> Result<(), Error> {
+    if template.is_empty() || template.len() > MAX_TEMPLATE_LEN {
+        return Err(Error::InvalidTemplate);
+    }
+
+    let template = TextBuffer::<TEMPLATE_CAPACITY>::from_string(template);
+    let bytes = template.as_bytes();
+    let mut slots = 0u32;
+    let mut badge_slots = 0u32;
+    let mut i = 0;
+    while i < bytes.len() {
+        match match_template_slot(&bytes[i..]) {
+            Some(slot) => {
+                slots += 1;
+                if slot == BADGES_SLOT {
+                    badge_slots += 1;
+                }
+                i += TEMPLATE_SLOTS[slot].len();
+            }
+            None => i += 1,
+        }
+    }
+
+    // Badge art is large, so a template may place it only once.
+    if slots > MAX_TEMPLATE_SLOTS || badge_slots > 1 {
+        return Err(Error::InvalidTemplate);
+    }
+    Ok(())
+}
+
+pub fn render_template(
+    env: &Env,
+    template: &String,
+    token_id: u64,
+    data: &GithubData,
+    badges: &Vec<String>,
+) -> String {
+    let template = TextBuffer::<TEMPLATE_CAPACITY>::from_string(template);
+    let username = TextBuffer::<USERNAME_CAPACITY>::from_string(&data.username);
+    let bytes = template.as_bytes();
+
+    let mut svg = TextBuffer::<SVG_CAPACITY>::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match match_template_slot(&bytes[i..]) {
+            Some(slot) => {
+                match slot {
+                    0 => svg.push_xml_escaped(username.as_bytes()),
+                    1 => svg.push_u64(data.contributions as u64),
+                    2 => svg.push_str(data.tier.label()),
+                    3 => svg.push_u64(token_id),
+                    _ => push_badges(&mut svg, badges),
+                }
+                i += TEMPLATE_SLOTS[slot].len();
+            }
+            None => {
+                svg.push_byte(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    svg.to_string(env)
+}
+
+// Converts days since the Unix epoch to a proleptic Gregorian (year, month, day),
+// following Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+pub(crate) fn metadata_json(
+    token_id: u64,
+    data: &GithubData,
+    svg: &String,
+) -> TextBuffer<JSON_CAPACITY> {
+    let theme = data.tier.theme();
+    let svg = TextBuffer::<SVG_CAPACITY>::from_string(svg);
+
+    let mut json = TextBuffer::new();
+    json.push_str("{\"name\":\"");
+    json.push_str(TOKEN_NAME);
+    json.push_str(" #");
+    json.push_u64(token_id);
+    json.push_str("\",\"description\":\"");
+    json.push_str(TOKEN_DESCRIPTION);
+    json.push_str("\",\"image\":\"data:image/svg+xml;base64,");
+    json.push_base64(svg.as_bytes());
+    json.push_str("\",\"attributes\":[{\"trait_type\":\"tier\",\"value\":\"");
+    json.push_str(theme.label);
+    json.push_str("\"},{\"trait_type\":\"color\",\"value\":\"");
+    json.push_str(theme.accent);
+    json.push_str("\"},{\"trait_type\":\"contributions\",\"value\":");
+    json.push_u64(data.contributions as u64);
+    json.push_str("},{\"trait_type\":\"minted_at\",\"display_type\":\"date\",\"value\":");
+    json.push_u64(data.minted_at);
+    json.push_str("},{\"trait_type\":\"updated_at\",\"display_type\":\"date\",\"value\":");
+    json.push_u64(data.updated_at);
+    json.push_str("}]}");
+    json
+}
+
+pub fn token_uri(env: &Env, token_id: u64, data: &GithubData, svg: &String) -> String {
+    let json = metadata_json(token_id, data, svg);
+
+    let mut uri = TextBuffer::<URI_CAPACITY>::new();
+    uri.push_str("data:application/json;base64,");
+    uri.push_base64(json.as_bytes());
+    uri.to_string(env)
+}